@@ -0,0 +1,6 @@
+#![no_main]
+
+use aoc2023::fuzz;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| fuzz::solve(2, fuzz::text(data)));
@@ -0,0 +1,126 @@
+//! Accepted answers of a day, one entry per input file, kept in
+//! `answers.toml` next to the inputs so that refactors cannot silently change
+//! them.
+//!
+//! ```toml
+//! [input]
+//! part1 = "114"
+//! part2 = "2"
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+pub const FILE_NAME: &str = "answers.toml";
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Expected {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+impl Expected {
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io(PathBuf, io::Error),
+    Toml(PathBuf, String),
+    NoSuchPart(u8),
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AnswersError::Io(path, err) => write!(f, "cannot access {}: {}", path.display(), err),
+            AnswersError::Toml(path, err) => write!(f, "invalid {}: {}", path.display(), err),
+            AnswersError::NoSuchPart(part) => write!(f, "there is no part {}", part),
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+/// Expected answers keyed by the name of the input file in the day's
+/// directory.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Answers {
+    pub inputs: BTreeMap<String, Expected>,
+}
+
+impl Answers {
+    /// Reads the answers at `path`, which are empty if the file does not
+    /// exist.
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(AnswersError::Io(path.to_path_buf(), err)),
+        };
+        toml::from_str(&content)
+            .map_err(|err| AnswersError::Toml(path.to_path_buf(), err.to_string()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AnswersError> {
+        let content = toml::to_string(self)
+            .map_err(|err| AnswersError::Toml(path.to_path_buf(), err.to_string()))?;
+        fs::write(path, content).map_err(|err| AnswersError::Io(path.to_path_buf(), err))
+    }
+
+    pub fn get(&self, input: &str, part: u8) -> Option<&str> {
+        self.inputs.get(input)?.part(part)
+    }
+
+    /// Records `answer` as the accepted answer of one part of `input`.
+    pub fn lock(&mut self, input: &str, part: u8, answer: &str) -> Result<(), AnswersError> {
+        let expected = self.inputs.entry(input.to_string()).or_default();
+        let slot = match part {
+            1 => &mut expected.part1,
+            2 => &mut expected.part2,
+            _ => return Err(AnswersError::NoSuchPart(part)),
+        };
+        *slot = Some(answer.to_string());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lock_and_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(FILE_NAME);
+        let mut answers = Answers::load(&path).unwrap();
+        assert_eq!(answers.get("input", 1), None);
+
+        answers.lock("input", 2, "46").unwrap();
+        answers.lock("input", 1, "35").unwrap();
+        answers.lock("examples/part1.txt", 1, "35").unwrap();
+        assert!(answers.lock("input", 3, "1").is_err());
+        answers.save(&path).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("[input]\npart1 = \"35\"\npart2 = \"46\"\n"));
+        let answers = Answers::load(&path).unwrap();
+        assert_eq!(answers.get("input", 2), Some("46"));
+        assert_eq!(answers.get("examples/part1.txt", 2), None);
+    }
+}
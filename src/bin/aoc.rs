@@ -0,0 +1,267 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use aoc2023::answers::Answers;
+use aoc2023::client::{self, Fetched};
+use aoc2023::config::{Config, INPUT};
+use aoc2023::days;
+use aoc2023::generate;
+use aoc2023::ledger::Verdict;
+use aoc2023::runner::{self, DayPart, Format, Matrix, Reader, Record};
+use aoc2023::scaffold;
+#[cfg(feature = "tui")]
+use aoc2023::tui;
+use aoc2023::utils;
+#[cfg(feature = "viz")]
+use aoc2023::viz;
+use clap::{Parser, Subcommand};
+
+#[derive(Parser)]
+struct Args {
+    /// Config file, defaults to aoc.toml when present
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// Server to talk to instead of the configured one
+    #[arg(long, global = true)]
+    base_url: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Download the puzzle input of a day unless it is already cached
+    Fetch {
+        #[arg(long)]
+        day: u32,
+    },
+    /// Print a random but valid input for a day
+    Gen {
+        #[arg(long)]
+        day: u32,
+
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Number of lines, cards, hands... in the input
+        #[arg(long, default_value_t = 100)]
+        size: usize,
+
+        /// File to write instead of standard output
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Create the solution module, binary and example files of a new day
+    New {
+        #[arg(long)]
+        day: u32,
+    },
+    /// Solve days on their inputs and compare with the accepted answers
+    Run {
+        /// Day to solve, defaults to every solved day
+        #[arg(long)]
+        day: Option<u32>,
+
+        /// Only re-solve parts with an accepted answer and fail if any
+        /// answer drifted
+        #[arg(long)]
+        check: bool,
+
+        /// Solve every .txt input in a directory, one per team member,
+        /// defaulting to the inputs/ directory of each day
+        #[arg(long, conflicts_with = "check")]
+        inputs: Option<Option<PathBuf>>,
+
+        /// How to read the inputs
+        #[arg(long, value_enum, default_value_t = Reader::Read)]
+        reader: Reader,
+
+        /// How to print the answers
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+
+        /// Part not to solve, such as 5.2 for the brute force of day 5
+        /// part 2; may be repeated
+        #[arg(long, value_name = "DAY.PART")]
+        skip: Vec<DayPart>,
+    },
+    /// Solve one part of a day and send the answer, unless the ledger
+    /// already shows it is wrong
+    Submit {
+        #[arg(long)]
+        day: u32,
+
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Input to solve, defaults to the cached input of the day
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Step through the solution of a day in the terminal
+    #[cfg(feature = "tui")]
+    Tui {
+        /// Day to start on, otherwise pick one
+        #[arg(long)]
+        day: Option<u32>,
+
+        /// Input to step through, defaults to the cached input of the day
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Draw the input of a day
+    #[cfg(feature = "viz")]
+    Viz {
+        #[arg(long)]
+        day: u32,
+
+        #[arg(long, value_enum)]
+        format: viz::Format,
+
+        /// Input to draw, defaults to the cached input of the day
+        #[arg(long)]
+        input: Option<PathBuf>,
+
+        /// File to write instead of standard output
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+}
+
+fn run(args: Args) -> Result<(), days::Error> {
+    let mut config = Config::load(args.config.as_deref())?;
+    if let Some(base_url) = args.base_url {
+        config.base_url = base_url;
+    }
+
+    match args.command {
+        Command::Fetch { day } => match client::fetch_input(&config, day)? {
+            Fetched::Cached(path) => println!("day {} already cached at {}", day, path.display()),
+            Fetched::Downloaded(path) => println!("day {} saved to {}", day, path.display()),
+        },
+        Command::Gen {
+            day,
+            seed,
+            size,
+            output,
+        } => {
+            let input = generate::generate(day, seed, size)?;
+            match output {
+                Some(path) => fs::write(path, input)?,
+                None => print!("{}", input),
+            }
+        }
+        Command::New { day } => {
+            for path in scaffold::scaffold(Path::new("."), &config, day)? {
+                println!("wrote {}", path.display());
+            }
+        }
+        Command::Run {
+            day,
+            check,
+            inputs,
+            reader,
+            format,
+            skip,
+        } => {
+            let days: Vec<u32> = match day {
+                Some(day) => vec![day],
+                None => days::days().collect(),
+            };
+            if day.is_none() && matches!(inputs, Some(Some(_))) {
+                return Err("an inputs directory needs --day".into());
+            }
+            let mut drifted = 0;
+            for day in days {
+                if let Some(dir) = &inputs {
+                    let dir = dir
+                        .clone()
+                        .unwrap_or_else(|| config.day_dir(day).join("inputs"));
+                    if inputs == Some(None) && !dir.is_dir() {
+                        continue;
+                    }
+                    let outcomes = runner::solve_all(
+                        &config,
+                        day,
+                        &runner::input_files(&dir)?,
+                        reader,
+                        &skip,
+                    )?;
+                    match format {
+                        Format::Text => print!("{}", Matrix(&outcomes)),
+                        Format::Json => {
+                            for outcome in &outcomes {
+                                println!("{}", Record::from(outcome));
+                            }
+                        }
+                    }
+                    drifted += outcomes.iter().filter(|outcome| outcome.drifted()).count();
+                    continue;
+                }
+                let outcomes = if check {
+                    runner::check(&config, day, reader, &skip)?
+                } else {
+                    runner::run(&config, day, reader, &skip)?
+                };
+                for outcome in outcomes {
+                    match format {
+                        Format::Text => println!("{}", outcome),
+                        Format::Json => println!("{}", Record::from(&outcome)),
+                    }
+                    if outcome.drifted() {
+                        drifted += 1;
+                    }
+                }
+            }
+            if drifted > 0 {
+                return Err(format!("{} answers drifted", drifted).into());
+            }
+        }
+        Command::Submit { day, part, input } => {
+            let solution = days::get(day).ok_or(format!("day {} is not solved", day))?;
+            let path = input.clone().unwrap_or_else(|| config.input_path(day));
+            let answer = solution.solve(part, &utils::read_file(&path)?)?;
+            println!("day {} part {}: {}", day, part, answer);
+            let verdict = client::submit_answer(&config, day, part, &answer)?;
+            println!("{}", verdict);
+            // Only the puzzle input has a place in answers.toml.
+            if verdict == Verdict::Correct && input.is_none() {
+                let path = config.answers_path(day);
+                let mut answers = Answers::load(&path)?;
+                answers.lock(INPUT, part, &answer)?;
+                answers.save(&path)?;
+            }
+        }
+        #[cfg(feature = "tui")]
+        Command::Tui { day, input } => tui::run(&config, day, input)?,
+        #[cfg(feature = "viz")]
+        Command::Viz {
+            day,
+            format,
+            input,
+            output,
+        } => {
+            let path = input.unwrap_or_else(|| config.input_path(day));
+            let picture = viz::render(day, format, &utils::read_file(&path)?)
+                .ok_or(format!("day {} cannot be drawn as {}", day, format))??;
+            match output {
+                Some(path) => fs::write(path, picture)?,
+                None => print!("{}", picture),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -1,118 +1,54 @@
+use aoc2023::days::day2::{default_bag, parse_bag, part1, part2, report, Bag};
 use aoc2023::utils;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, alpha1, line_ending},
-    multi::separated_list1,
-    sequence::{preceded, separated_pair},
-    IResult,
-};
+use clap::Parser;
 
-#[derive(Debug)]
-struct Cube<'a> {
-    color: &'a str,
-    amount: u32,
-}
-
-#[derive(Debug)]
-struct Game<'a> {
-    id: u32,
-    rounds: Vec<Vec<Cube<'a>>>,
-}
-
-fn cube(input: &str) -> IResult<&str, Cube> {
-    let (input, (amount, color)) = separated_pair(complete::u32, tag(" "), alpha1)(input)?;
-    Ok((input, Cube { color, amount }))
-}
+#[derive(Parser)]
+struct Args {
+    /// Bag contents in round format, e.g. "12 red, 13 green, 14 blue"
+    #[arg(long, conflicts_with = "bag_file")]
+    bag: Option<String>,
 
-fn round(input: &str) -> IResult<&str, Vec<Cube>> {
-    let (input, cubes) = separated_list1(tag(", "), cube)(input)?;
-    Ok((input, cubes))
-}
+    /// File holding the bag contents in round format
+    #[arg(long)]
+    bag_file: Option<String>,
 
-fn game(input: &str) -> IResult<&str, Game> {
-    let (input, id) = preceded(tag("Game "), complete::u32)(input)?;
-    let (input, rounds) = preceded(tag(": "), separated_list1(tag("; "), round))(input)?;
-    Ok((input, Game { id, rounds }))
-}
+    /// Print a per-game report instead of the puzzle answers
+    #[arg(long)]
+    report: bool,
 
-fn parse_games(input: &str) -> IResult<&str, Vec<Game>> {
-    let (input, games) = separated_list1(line_ending, game)(input)?;
-    Ok((input, games))
+    #[arg(default_value = "src/data/day2/input")]
+    input: String,
 }
 
-fn check_game(game: &Game, bag_size: &[u32]) -> bool {
-    for round in &game.rounds {
-        for cube in round {
-            match cube.color {
-                "red" => {
-                    if cube.amount > bag_size[0] {
-                        return false;
-                    }
-                }
-                "green" => {
-                    if cube.amount > bag_size[1] {
-                        return false;
-                    }
-                }
-                "blue" => {
-                    if cube.amount > bag_size[2] {
-                        return false;
-                    }
-                }
-                _ => println!("Impossible color"),
-            }
-        }
+fn load_bag(args: &Args) -> Result<Bag, Box<dyn std::error::Error>> {
+    if let Some(bag) = &args.bag {
+        return Ok(parse_bag(bag)?);
     }
-    true
-}
-
-fn get_game_power(game: &Game) -> u32 {
-    let mut min_cubes = vec![0, 0, 0];
-    for round in &game.rounds {
-        for cube in round {
-            match cube.color {
-                "red" => {
-                    if cube.amount > min_cubes[0] {
-                        min_cubes[0] = cube.amount;
-                    }
-                }
-                "green" => {
-                    if cube.amount > min_cubes[1] {
-                        min_cubes[1] = cube.amount;
-                    }
-                }
-                "blue" => {
-                    if cube.amount > min_cubes[2] {
-                        min_cubes[2] = cube.amount;
-                    }
-                }
-                _ => println!("Impossible color"),
-            }
-        }
+    if let Some(path) = &args.bag_file {
+        return Ok(parse_bag(&utils::read_file(path)?)?);
     }
-    min_cubes[0] * min_cubes[1] * min_cubes[2]
-}
-
-fn part1(input: &str) -> u32 {
-    let (_, games) = parse_games(input).unwrap();
-    let bag_size = vec![12, 13, 14];
-    games
-        .iter()
-        .filter(|game| check_game(game, &bag_size))
-        .map(|game| game.id)
-        .sum()
-}
-
-fn part2(input: &str) -> u32 {
-    let (_, games) = parse_games(input).unwrap();
-    games.iter().map(get_game_power).sum()
+    Ok(default_bag())
 }
 
 fn main() {
-    match utils::read_file("src/data/day2/input") {
-        Ok(content) => {
-            println!("part1: {}; part2: {}", part1(&content), part2(&content));
+    let args = Args::parse();
+    let bag = match load_bag(&args) {
+        Ok(bag) => bag,
+        Err(err) => {
+            eprintln!("Error loading bag: {}", err);
+            return;
         }
+    };
+
+    match utils::read_file(&args.input) {
+        Ok(content) if args.report => match report(&content, &bag) {
+            Ok(report) => print!("{}", report),
+            Err(err) => eprintln!("Error: {}", err),
+        },
+        Ok(content) => match (part1(&content, &bag), part2(&content, &bag)) {
+            (Ok(part1), Ok(part2)) => println!("part1: {}; part2: {}", part1, part2),
+            (Err(err), _) | (_, Err(err)) => eprintln!("Error: {}", err),
+        },
         Err(err) => {
             eprintln!("Error reading file: {}", err);
         }
@@ -1,156 +1,53 @@
+use aoc2023::days::day3::{list_symbols, part1, part2, Aggregation, GearRule, Schematic};
 use aoc2023::utils;
-use std::cmp::max;
-use std::cmp::min;
+use clap::Parser;
 
-struct PartNumber {
-    number: u32,
-    positions: Vec<(usize, usize)>,
-}
-
-fn neighbors(lines: &Vec<Vec<char>>, x: i32, y: i32) -> Vec<char> {
-    let mut neighbors: Vec<char> = vec![];
-    let rows = lines.len() as i32;
-    let cols = lines[0].len() as i32;
-
-    (max(0, x - 1)..=min(x + 1, rows - 1)).for_each(|i| {
-        (max(0, y - 1)..=min(y + 1, cols - 1)).for_each(|j| {
-            if x != i || y != j {
-                neighbors.push(lines[i as usize][j as usize]);
-            }
-        });
-    });
+#[derive(Parser)]
+struct Args {
+    /// Symbols that count as gears
+    #[arg(long, default_value = "*")]
+    gear_symbols: String,
 
-    neighbors
-}
+    /// Number of adjacent part numbers a gear needs
+    #[arg(long, default_value_t = 2)]
+    gear_neighbors: usize,
 
-fn neighbors2(lines: &Vec<Vec<char>>, x: i32, y: i32) -> Vec<(i32, i32)> {
-    let mut neighbors: Vec<(i32, i32)> = vec![];
-    let rows = lines.len() as i32;
-    let cols = lines[0].len() as i32;
+    /// How the adjacent part numbers are combined into the gear ratio
+    #[arg(long, value_enum, default_value_t = Aggregation::Product)]
+    aggregation: Aggregation,
 
-    (max(0, x - 1)..=min(x + 1, rows - 1)).for_each(|i| {
-        (max(0, y - 1)..=min(y + 1, cols - 1)).for_each(|j| {
-            if x != i || y != j {
-                neighbors.push((i, j));
-            }
-        });
-    });
+    /// List every symbol with its adjacent numbers
+    #[arg(long)]
+    list: bool,
 
-    neighbors
-}
-
-fn get_neighbor_part_numbers(lines: &Vec<Vec<char>>, x: usize, y: usize) -> Vec<u32> {
-    lines
-        .iter()
-        .enumerate()
-        .skip(max(0, x - 1))
-        .take(3)
-        .flat_map(|(index, line)| extract_numbers(line.clone(), index))
-        .filter(
-            |PartNumber {
-                 number: _,
-                 positions,
-             }| {
-                let neighbors = neighbors2(lines, x as i32, y as i32);
-                neighbors.iter().any(|(i, j)| {
-                    positions
-                        .iter()
-                        .any(|(m, n)| *i as usize == *m && *j as usize == *n)
-                })
-            },
-        )
-        .map(
-            |PartNumber {
-                 number,
-                 positions: _,
-             }| number,
-        )
-        .collect()
-}
-
-fn extract_numbers(line: Vec<char>, line_index: usize) -> Vec<PartNumber> {
-    let mut numbers: Vec<PartNumber> = vec![];
-    let mut tmp_number: u32 = 0;
-    let mut positions: Vec<(usize, usize)> = vec![];
-
-    for (i, c) in line.iter().enumerate() {
-        if c.is_ascii_digit() {
-            tmp_number = tmp_number * 10 + c.to_digit(10).unwrap();
-            positions.push((line_index, i));
-        } else if tmp_number != 0 {
-            numbers.push(PartNumber {
-                number: tmp_number,
-                positions: positions.clone(),
-            });
-            tmp_number = 0;
-            positions.clear();
-        }
-    }
-    if tmp_number != 0 {
-        numbers.push(PartNumber {
-            number: tmp_number,
-            positions: positions.clone(),
-        });
-    }
-
-    numbers
-}
-
-fn extract_part_numbers(lines: &Vec<Vec<char>>, numbers: Vec<PartNumber>) -> Vec<u32> {
-    numbers
-        .iter()
-        .filter(|number| {
-            let neighbors: String = number
-                .positions
-                .iter()
-                .flat_map(|(i, j)| {
-                    neighbors(lines, (*i).try_into().unwrap(), (*j).try_into().unwrap())
-                })
-                .collect();
-            let symbols: String = neighbors
-                .chars()
-                .filter(|value| !(value.is_ascii_digit() || *value == '.'))
-                .collect();
-            !symbols.is_empty()
-        })
-        .map(
-            |PartNumber {
-                 number,
-                 positions: _,
-             }| *number,
-        )
-        .collect()
+    #[arg(default_value = "src/data/day3/input")]
+    input: String,
 }
 
 fn main() {
-    match utils::read_file("src/data/day3/input") {
-        Ok(content) => {
-            let lines: Vec<Vec<char>> = content
-                .lines()
-                .map(|line| line.chars().collect::<Vec<_>>())
-                .collect();
-
-            let sum_of_components: u32 = lines
-                .iter()
-                .enumerate()
-                .map(|(line_index, line)| extract_numbers(line.to_vec(), line_index))
-                .flat_map(|numbers| extract_part_numbers(&lines, numbers))
-                .sum();
-            println!("part1: {}", sum_of_components);
-
-            let mut sum: u32 = 0;
-            (0..lines.len()).for_each(|i| {
-                (0..lines[0].len()).for_each(|j| {
-                    if lines[i][j] == '*' {
-                        let neighbors = get_neighbor_part_numbers(&lines, i, j);
-                        if neighbors.len() == 2 {
-                            sum += neighbors[0] * neighbors[1];
-                        }
+    let args = Args::parse();
+    let rule = GearRule {
+        symbols: args.gear_symbols.chars().collect(),
+        neighbors: args.gear_neighbors,
+        aggregation: args.aggregation,
+    };
+
+    match utils::read_file(&args.input) {
+        Ok(content) => match Schematic::parse(&content) {
+            Ok(schematic) => {
+                if args.list {
+                    print!("{}", list_symbols(&schematic));
+                }
+                match (part1(&schematic), part2(&schematic, &rule)) {
+                    (Ok(part1), Ok(part2)) => {
+                        println!("part1: {}", part1);
+                        println!("part2: {}", part2);
                     }
-                })
-            });
-            println!("part2: {}", sum);
-        }
+                    (Err(err), _) | (_, Err(err)) => eprintln!("Error: {}", err),
+                }
+            }
+            Err(err) => eprintln!("Error: {}", err),
+        },
         Err(err) => {
             eprintln!("Error reading file: {}", err);
         }
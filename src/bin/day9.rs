@@ -1,64 +1,37 @@
+use aoc2023::days::day9::{explain, part1, part2};
 use aoc2023::utils;
+use clap::Parser;
 
-fn predict(values: &[i64]) -> i64 {
-    if values.iter().all(|value| *value == 0) {
-        0
-    } else {
-        let mut new_values = vec![];
-        for i in 1..values.len() {
-            new_values.push(values[i] - values[i - 1]);
-        }
-        values.last().unwrap() + predict(&new_values)
-    }
-}
-
-fn predict_past(values: &[i64]) -> i64 {
-    if values.iter().all(|value| *value == 0) {
-        0
-    } else {
-        let mut new_values = vec![];
-        for i in 1..values.len() {
-            new_values.push(values[i] - values[i - 1]);
-        }
-        values.first().unwrap() - predict_past(&new_values)
-    }
-}
-
-fn part1(input: &str) -> i64 {
-    let mut histories: Vec<Vec<i64>> = vec![];
-
-    for line in input.lines() {
-        let mut history: Vec<i64> = vec![];
-        for number in line.split(' ') {
-            history.push(number.parse::<i64>().unwrap())
-        }
-
-        histories.push(history)
-    }
-
-    histories.iter().map(|history| predict(history)).sum()
-}
-
-fn part2(input: &str) -> i64 {
-    let mut histories: Vec<Vec<i64>> = vec![];
-
-    for line in input.lines() {
-        let mut history: Vec<i64> = vec![];
-        for number in line.split(' ') {
-            history.push(number.parse::<i64>().unwrap())
-        }
-
-        histories.push(history)
-    }
+#[derive(Parser)]
+struct Args {
+    /// Print the pyramid of differences of every history
+    #[arg(long)]
+    explain: bool,
 
-    histories.iter().map(|history| predict_past(history)).sum()
+    #[arg(default_value = "src/data/day9/input")]
+    input: String,
 }
 
 fn main() {
-    match utils::read_file("src/data/day9/input") {
+    let args = Args::parse();
+    match utils::read_file(&args.input) {
         Ok(input) => {
-            println!("{}", part1(&input));
-            println!("{}", part2(&input));
+            if args.explain {
+                match explain(&input) {
+                    Ok(pyramids) => print!("{}", pyramids),
+                    Err(err) => {
+                        eprintln!("Error: {}", err);
+                        return;
+                    }
+                }
+            }
+            match (part1(&input), part2(&input)) {
+                (Ok(part1), Ok(part2)) => {
+                    println!("{}", part1);
+                    println!("{}", part2);
+                }
+                (Err(err), _) | (_, Err(err)) => eprintln!("Error: {}", err),
+            }
         }
         Err(err) => {
             eprintln!("Error reading file: {}", err);
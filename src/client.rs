@@ -0,0 +1,331 @@
+//! Blocking client for the Advent of Code website.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::ledger::{Ledger, LedgerError, Refusal, Verdict};
+
+const USER_AGENT: &str = "github.com/rrentea/AdventOfCode2023";
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// No session token in the config or the `AOC_SESSION` variable.
+    MissingSession,
+    /// The server answered with an error status.
+    Status(u16, String),
+    /// The server could not be reached.
+    Transport(String),
+    /// The answer page did not contain a verdict we know about.
+    UnknownVerdict(String),
+    Refused(Refusal),
+    Ledger(LedgerError),
+    Io(io::Error),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientError::MissingSession => {
+                write!(
+                    f,
+                    "no session token, set `session` in aoc.toml or AOC_SESSION"
+                )
+            }
+            ClientError::Status(status, body) => {
+                write!(f, "server answered {}: {}", status, body.trim())
+            }
+            ClientError::Transport(err) => write!(f, "{}", err),
+            ClientError::UnknownVerdict(message) => {
+                write!(f, "unrecognised answer page: {}", message)
+            }
+            ClientError::Refused(refusal) => write!(f, "not submitting: {}", refusal),
+            ClientError::Ledger(err) => write!(f, "{}", err),
+            ClientError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<io::Error> for ClientError {
+    fn from(err: io::Error) -> Self {
+        ClientError::Io(err)
+    }
+}
+
+impl From<LedgerError> for ClientError {
+    fn from(err: LedgerError) -> Self {
+        ClientError::Ledger(err)
+    }
+}
+
+impl From<Refusal> for ClientError {
+    fn from(refusal: Refusal) -> Self {
+        ClientError::Refused(refusal)
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(err: ureq::Error) -> Self {
+        match err {
+            ureq::Error::Status(status, response) => {
+                ClientError::Status(status, response.into_string().unwrap_or_default())
+            }
+            ureq::Error::Transport(err) => ClientError::Transport(err.to_string()),
+        }
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    year: u32,
+    session: String,
+}
+
+impl Client {
+    pub fn new(config: &Config) -> Result<Self, ClientError> {
+        Ok(Self {
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            year: config.year,
+            session: config.session.clone().ok_or(ClientError::MissingSession)?,
+        })
+    }
+
+    fn request(&self, method: &str, path: &str) -> ureq::Request {
+        ureq::request(method, &format!("{}/{}{}", self.base_url, self.year, path))
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+    }
+
+    fn get(&self, path: &str) -> Result<String, ClientError> {
+        Ok(self.request("GET", path).call()?.into_string()?)
+    }
+
+    /// Puzzle input of `day`.
+    pub fn input(&self, day: u32) -> Result<String, ClientError> {
+        self.get(&format!("/day/{}/input", day))
+    }
+
+    /// Sends `answer` for one part of `day` and reads the verdict off the
+    /// page the server answers with.
+    pub fn submit(&self, day: u32, part: u8, answer: &str) -> Result<Verdict, ClientError> {
+        let page = self
+            .request("POST", &format!("/day/{}/answer", day))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+        verdict(&page)
+    }
+}
+
+fn verdict(page: &str) -> Result<Verdict, ClientError> {
+    if page.contains("That's the right answer") {
+        Ok(Verdict::Correct)
+    } else if page.contains("You gave an answer too recently") {
+        Ok(Verdict::Wait)
+    } else if page.contains("your answer is too high") {
+        Ok(Verdict::TooHigh)
+    } else if page.contains("your answer is too low") {
+        Ok(Verdict::TooLow)
+    } else if page.contains("That's not the right answer") {
+        Ok(Verdict::Wrong)
+    } else {
+        Err(ClientError::UnknownVerdict(article(page)))
+    }
+}
+
+/// Text of the `<article>` holding the server's message, without markup.
+fn article(page: &str) -> String {
+    let start = page
+        .find("<article>")
+        .map_or(0, |start| start + "<article>".len());
+    let end = page[start..]
+        .find("</article>")
+        .map_or(page.len(), |end| start + end);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in page[start..end].chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    /// The input was already in the cache and the server was not contacted.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Makes sure the input of `day` is in the cache directory, downloading it
+/// only if it is not there yet.
+pub fn fetch_input(config: &Config, day: u32) -> Result<Fetched, ClientError> {
+    let path = config.input_path(day);
+    if path.exists() {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let input = Client::new(config)?.input(day)?;
+    fs::create_dir_all(path.parent().unwrap())?;
+    // Write next to the target and rename, so an interrupted download is
+    // never mistaken for a cached input.
+    let partial = path.with_extension("partial");
+    fs::write(&partial, input)?;
+    fs::rename(&partial, &path)?;
+    Ok(Fetched::Downloaded(path))
+}
+
+/// Submits `answer` unless the ledger shows it cannot be right, then records
+/// the verdict in the ledger.
+pub fn submit_answer(
+    config: &Config,
+    day: u32,
+    part: u8,
+    answer: &str,
+) -> Result<Verdict, ClientError> {
+    let mut ledger = Ledger::load(&config.ledger)?;
+    ledger.check(day, part, answer)?;
+
+    let verdict = Client::new(config)?.submit(day, part, answer)?;
+    ledger.record(day, part, answer, verdict);
+    ledger.save(&config.ledger)?;
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub_server::StubServer;
+
+    fn config(url: &str, cache_dir: &std::path::Path) -> Config {
+        Config {
+            session: Some("secret".to_string()),
+            base_url: url.to_string(),
+            year: 2023,
+            cache_dir: cache_dir.to_path_buf(),
+            ledger: cache_dir.join("ledger.toml"),
+        }
+    }
+
+    #[test]
+    fn fetch_downloads_once() {
+        let cache = tempfile::tempdir().unwrap();
+        let server = StubServer::start(vec![(200, "1 2 3\n")]);
+        let config = config(&server.url, cache.path());
+        let path = cache.path().join("day9").join("input");
+
+        assert_eq!(
+            fetch_input(&config, 9).unwrap(),
+            Fetched::Downloaded(path.clone())
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 2 3\n");
+        // The stub only answers once, so a second download would fail.
+        assert_eq!(fetch_input(&config, 9).unwrap(), Fetched::Cached(path));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2023/day/9/input");
+        assert_eq!(requests[0].header("Cookie"), Some("session=secret"));
+    }
+
+    #[test]
+    fn failed_fetch_is_not_cached() {
+        let cache = tempfile::tempdir().unwrap();
+        let server = StubServer::start(vec![(400, "Please log in")]);
+        let config = config(&server.url, cache.path());
+
+        match fetch_input(&config, 1) {
+            Err(ClientError::Status(400, body)) => assert_eq!(body, "Please log in"),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(!config.input_path(1).exists());
+        server.requests();
+    }
+
+    #[test]
+    fn fetch_needs_a_session() {
+        let cache = tempfile::tempdir().unwrap();
+        let config = Config {
+            session: None,
+            ..config("http://127.0.0.1:9", cache.path())
+        };
+        assert!(matches!(
+            fetch_input(&config, 1),
+            Err(ClientError::MissingSession)
+        ));
+    }
+
+    const TOO_HIGH: &str = "<main><article><p>That's not the right answer; \
+        your answer is too high.  If you're stuck, ...</p></article></main>";
+    const CORRECT: &str = "<main><article><p>That's the right answer!  \
+        You are <span class=\"day-success\">one gold star</span> closer.</p></article></main>";
+
+    #[test]
+    fn submit_records_verdicts() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = StubServer::start(vec![(200, TOO_HIGH), (200, CORRECT)]);
+        let config = config(&server.url, dir.path());
+
+        assert_eq!(
+            submit_answer(&config, 5, 2, "900").unwrap(),
+            Verdict::TooHigh
+        );
+        // Refused without contacting the server.
+        assert!(matches!(
+            submit_answer(&config, 5, 2, "1000"),
+            Err(ClientError::Refused(Refusal::TooHigh(_)))
+        ));
+        assert_eq!(
+            submit_answer(&config, 5, 2, "46").unwrap(),
+            Verdict::Correct
+        );
+        assert!(matches!(
+            submit_answer(&config, 5, 2, "46"),
+            Err(ClientError::Refused(Refusal::AlreadySolved(_)))
+        ));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2023/day/5/answer");
+        assert_eq!(requests[0].body, "level=2&answer=900");
+        assert_eq!(requests[1].body, "level=2&answer=46");
+
+        let ledger = Ledger::load(&config.ledger).unwrap();
+        let verdicts: Vec<_> = ledger
+            .submissions
+            .iter()
+            .map(|submission| (submission.answer.as_str(), submission.verdict))
+            .collect();
+        assert_eq!(
+            verdicts,
+            [("900", Verdict::TooHigh), ("46", Verdict::Correct)]
+        );
+    }
+
+    #[test]
+    fn verdicts_are_read_from_the_page() {
+        assert_eq!(verdict(TOO_HIGH).unwrap(), Verdict::TooHigh);
+        assert_eq!(verdict(CORRECT).unwrap(), Verdict::Correct);
+        assert_eq!(
+            verdict("<article><p>You gave an answer too recently; wait.</p></article>").unwrap(),
+            Verdict::Wait
+        );
+        match verdict(
+            "<article><p>You don't seem to be <em>solving</em> the right level.</p></article>",
+        ) {
+            Err(ClientError::UnknownVerdict(message)) => {
+                assert_eq!(message, "You don't seem to be solving the right level.")
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Settings for talking to the Advent of Code server, read from `aoc.toml`.
+//!
+//! ```toml
+//! session = "53616c7465645f5f..."
+//! base_url = "https://adventofcode.com"
+//! year = 2023
+//! cache_dir = "src/data"
+//! ledger = "ledger.toml"
+//! ```
+//!
+//! Every key is optional. The `AOC_SESSION` environment variable takes
+//! precedence over `session` so the token can stay out of the file.
+
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::answers;
+
+pub const DEFAULT_PATH: &str = "aoc.toml";
+
+/// File name of the puzzle input inside a day's directory.
+pub const INPUT: &str = "input";
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Value of the `session` cookie of a logged in browser.
+    pub session: Option<String>,
+    /// Server to talk to, without a trailing slash.
+    pub base_url: String,
+    pub year: u32,
+    /// Directory holding one `day<N>/input` file per fetched day.
+    pub cache_dir: PathBuf,
+    /// File recording every submitted answer and its verdict.
+    pub ledger: PathBuf,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            session: None,
+            base_url: "https://adventofcode.com".to_string(),
+            year: 2023,
+            cache_dir: PathBuf::from("src/data"),
+            ledger: PathBuf::from("ledger.toml"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, io::Error),
+    Toml(PathBuf, toml::de::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, err) => write!(f, "cannot read {}: {}", path.display(), err),
+            ConfigError::Toml(path, err) => write!(f, "invalid {}: {}", path.display(), err),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// Reads the config file, falling back to the defaults when `path` is
+    /// `None` and there is no `aoc.toml` in the working directory.
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigError> {
+        let mut config = match path {
+            Some(path) => Self::read(path)?,
+            None if Path::new(DEFAULT_PATH).exists() => Self::read(Path::new(DEFAULT_PATH))?,
+            None => Self::default(),
+        };
+        if let Ok(session) = std::env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        Ok(config)
+    }
+
+    fn read(path: &Path) -> Result<Self, ConfigError> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|err| ConfigError::Io(path.to_path_buf(), err))?;
+        toml::from_str(&content).map_err(|err| ConfigError::Toml(path.to_path_buf(), err))
+    }
+
+    /// Directory holding the inputs and accepted answers of `day`.
+    pub fn day_dir(&self, day: u32) -> PathBuf {
+        self.cache_dir.join(format!("day{}", day))
+    }
+
+    /// Where the input of `day` is cached.
+    pub fn input_path(&self, day: u32) -> PathBuf {
+        self.day_dir(day).join(INPUT)
+    }
+
+    /// Where the accepted answers of `day` are kept.
+    pub fn answers_path(&self, day: u32) -> PathBuf {
+        self.day_dir(day).join(answers::FILE_NAME)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial_file_keeps_defaults() {
+        let config: Config = toml::from_str("base_url = \"http://127.0.0.1:8080\"").unwrap();
+        assert_eq!(
+            config,
+            Config {
+                base_url: "http://127.0.0.1:8080".to_string(),
+                ..Config::default()
+            }
+        );
+        assert_eq!(config.input_path(5), Path::new("src/data/day5/input"));
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        assert!(toml::from_str::<Config>("sesion = \"abc\"").is_err());
+    }
+}
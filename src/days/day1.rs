@@ -0,0 +1,131 @@
+use std::io;
+
+use super::{no_such_part, Error, Lines, Solution};
+
+/// Spelled out digits, which only count in part 2.
+const WORDS: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+/// Digit at byte `i` of `s`, written or, if `digits` has words, spelled out.
+fn digit_at(s: &str, i: usize, c: char, digits: &[(&str, u32)]) -> Option<u32> {
+    if c.is_ascii_digit() {
+        return c.to_digit(10);
+    }
+    digits
+        .iter()
+        .find(|(word, _)| s[i..].starts_with(word))
+        .map(|(_, digit)| *digit)
+}
+
+fn get_first_digit(s: &str, digits: &[(&str, u32)]) -> Option<u32> {
+    s.char_indices()
+        .find_map(|(i, c)| digit_at(s, i, c, digits))
+}
+
+fn get_last_digit(s: &str, digits: &[(&str, u32)]) -> Option<u32> {
+    s.char_indices()
+        .rev()
+        .find_map(|(i, c)| digit_at(s, i, c, digits))
+}
+
+fn get_calibration_values(s: &str, digits: &[(&str, u32)]) -> Result<u32, Error> {
+    let first_digit = get_first_digit(s, digits);
+    let last_digit = get_last_digit(s, digits);
+    match (first_digit, last_digit) {
+        (Some(first_digit), Some(last_digit)) => Ok(first_digit * 10 + last_digit),
+        _ => Err(format!("no digit in '{}'", s).into()),
+    }
+}
+
+/// Sum of the calibration values of `lines`, which may come straight from a
+/// file. Values are at most 99, so a `u64` holds the sum of far more lines
+/// than any file can.
+pub fn calibration_sum<S: AsRef<str>>(
+    lines: impl Iterator<Item = io::Result<S>>,
+    digits: &[(&str, u32)],
+) -> Result<u64, Error> {
+    let mut sum = 0;
+    for line in lines {
+        sum += u64::from(get_calibration_values(line?.as_ref(), digits)?);
+    }
+    Ok(sum)
+}
+
+pub fn part1(input: &str) -> Result<u64, Error> {
+    calibration_sum(input.lines().map(Ok), &[])
+}
+
+pub fn part2(input: &str) -> Result<u64, Error> {
+    calibration_sum(input.lines().map(Ok), &WORDS)
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn part1(&self, input: &str) -> Result<String, Error> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, Error> {
+        Ok(part2(input)?.to_string())
+    }
+
+    fn solve_lines(&self, part: u8, lines: Lines) -> Result<String, Error> {
+        let digits: &[(&str, u32)] = match part {
+            1 => &[],
+            2 => &WORDS,
+            _ => return Err(no_such_part(part)),
+        };
+        Ok(calibration_sum(lines, digits)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../data/day1/examples/part2.txt");
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            part1(include_str!("../data/day1/examples/part1.txt")).unwrap(),
+            142
+        );
+        assert_eq!(part2(EXAMPLE).unwrap(), 281);
+    }
+
+    #[test]
+    fn crlf_and_unterminated_input() {
+        for input in [
+            EXAMPLE.replace('\n', "\r\n"),
+            EXAMPLE.replace('\n', " \n"),
+            EXAMPLE.trim_end().to_string(),
+        ] {
+            assert_eq!(part2(&input).unwrap(), 281);
+        }
+    }
+
+    #[test]
+    fn streamed_lines() {
+        let input = EXAMPLE.replace('\n', "\r\n");
+        let mut lines = crate::utils::Lines::new(input.as_bytes());
+        assert_eq!(Day1.solve_lines(2, &mut lines).unwrap(), "281");
+    }
+
+    #[test]
+    fn non_ascii_lines() {
+        assert_eq!(part1("pqr\u{3de}\u{525}3stu8vwx\n").unwrap(), 38);
+        assert_eq!(part2("\u{e9}twone\u{e9}\n").unwrap(), 21);
+        assert!(part2("\u{e9}\n").is_err());
+    }
+}
@@ -0,0 +1,446 @@
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+
+use super::{Error, Solution};
+use crate::parse::{self, ParseError};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{self, alpha1},
+    combinator::{all_consuming, map},
+    multi::separated_list1,
+    sequence::{preceded, separated_pair},
+    IResult,
+};
+
+/// Number of cubes of each colour in the bag.
+pub type Bag = HashMap<String, u32>;
+
+#[derive(Debug, PartialEq)]
+pub struct Cube<'a> {
+    pub color: &'a str,
+    pub amount: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Game<'a> {
+    pub id: u32,
+    pub rounds: Vec<Vec<Cube<'a>>>,
+}
+
+impl fmt::Display for Cube<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.amount, self.color)
+    }
+}
+
+impl fmt::Display for Game<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Game {}:", self.id)?;
+        for (index, round) in self.rounds.iter().enumerate() {
+            write!(f, "{}", if index == 0 { " " } else { "; " })?;
+            for (index, cube) in round.iter().enumerate() {
+                if index > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", cube)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A cube count in a round that exceeds what the bag holds.
+#[derive(Debug, PartialEq)]
+pub struct Violation {
+    pub round: usize,
+    pub color: String,
+    pub amount: u32,
+    pub available: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum GameError {
+    UnknownColor {
+        game: u32,
+        color: String,
+    },
+    InvalidBag(String),
+    Parse(ParseError),
+    /// A power or sum that does not fit in a `u32`.
+    Overflow,
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameError::UnknownColor { game, color } => {
+                write!(
+                    f,
+                    "game {} uses colour '{}' which is not in the bag",
+                    game, color
+                )
+            }
+            GameError::InvalidBag(bag) => write!(f, "invalid bag '{}'", bag),
+            GameError::Parse(err) => write!(f, "{}", err),
+            GameError::Overflow => write!(f, "the answer does not fit in a u32"),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+impl From<ParseError> for GameError {
+    fn from(err: ParseError) -> Self {
+        GameError::Parse(err)
+    }
+}
+
+pub fn cube(input: &str) -> IResult<&str, Cube<'_>> {
+    let (input, (amount, color)) = separated_pair(complete::u32, tag(" "), alpha1)(input)?;
+    Ok((input, Cube { color, amount }))
+}
+
+pub fn round(input: &str) -> IResult<&str, Vec<Cube<'_>>> {
+    let (input, cubes) = separated_list1(tag(", "), cube)(input)?;
+    Ok((input, cubes))
+}
+
+pub fn game(input: &str) -> IResult<&str, Game<'_>> {
+    map(
+        parse::labelled(
+            preceded(tag("Game "), complete::u32),
+            separated_list1(tag("; "), round),
+        ),
+        |(id, rounds)| Game { id, rounds },
+    )(input)
+}
+
+pub fn parse_games(input: &str) -> Result<Vec<Game<'_>>, GameError> {
+    Ok(parse::parse_all(parse::lines(game), input)?)
+}
+
+pub fn parse_bag(input: &str) -> Result<Bag, GameError> {
+    let (_, cubes) = all_consuming(round)(input.trim())
+        .map_err(|_| GameError::InvalidBag(input.trim().to_string()))?;
+    let mut bag = Bag::new();
+    for cube in cubes {
+        *bag.entry(cube.color.to_string()).or_insert(0) += cube.amount;
+    }
+    Ok(bag)
+}
+
+pub fn default_bag() -> Bag {
+    parse_bag("12 red, 13 green, 14 blue").unwrap()
+}
+
+pub fn format_bag(bag: &Bag) -> String {
+    let mut colors: Vec<_> = bag.iter().collect();
+    colors.sort();
+    colors
+        .iter()
+        .map(|(color, amount)| format!("{} {}", amount, color))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub fn violations(game: &Game, bag: &Bag) -> Result<Vec<Violation>, GameError> {
+    let mut violations = vec![];
+    for (index, round) in game.rounds.iter().enumerate() {
+        for cube in round {
+            match bag.get(cube.color) {
+                Some(size) => {
+                    if cube.amount > *size {
+                        violations.push(Violation {
+                            round: index + 1,
+                            color: cube.color.to_string(),
+                            amount: cube.amount,
+                            available: *size,
+                        });
+                    }
+                }
+                None => {
+                    return Err(GameError::UnknownColor {
+                        game: game.id,
+                        color: cube.color.to_string(),
+                    })
+                }
+            }
+        }
+    }
+    Ok(violations)
+}
+
+pub fn check_game(game: &Game, bag: &Bag) -> Result<bool, GameError> {
+    Ok(violations(game, bag)?.is_empty())
+}
+
+/// Ids of the games that are possible with the given bag.
+pub fn possible_games(games: &[Game], bag: &Bag) -> Result<Vec<u32>, GameError> {
+    let mut ids = vec![];
+    for game in games {
+        if check_game(game, bag)? {
+            ids.push(game.id);
+        }
+    }
+    Ok(ids)
+}
+
+/// Smallest bag with which the game is possible.
+pub fn game_bag(game: &Game) -> Bag {
+    let mut bag = Bag::new();
+    for round in &game.rounds {
+        for cube in round {
+            let min = bag.entry(cube.color.to_string()).or_insert(0);
+            if cube.amount > *min {
+                *min = cube.amount;
+            }
+        }
+    }
+    bag
+}
+
+/// Smallest bag with which every game is possible.
+pub fn minimal_bag(games: &[Game]) -> Bag {
+    let mut bag = Bag::new();
+    for game in games {
+        for (color, amount) in game_bag(game) {
+            let min = bag.entry(color).or_insert(0);
+            if amount > *min {
+                *min = amount;
+            }
+        }
+    }
+    bag
+}
+
+pub fn get_game_power(game: &Game, bag: &Bag) -> Result<u32, GameError> {
+    let mut min_cubes: HashMap<&str, u32> = bag.keys().map(|color| (color.as_str(), 0)).collect();
+    for round in &game.rounds {
+        for cube in round {
+            match min_cubes.get_mut(cube.color) {
+                Some(min) => {
+                    if cube.amount > *min {
+                        *min = cube.amount;
+                    }
+                }
+                None => {
+                    return Err(GameError::UnknownColor {
+                        game: game.id,
+                        color: cube.color.to_string(),
+                    })
+                }
+            }
+        }
+    }
+    min_cubes
+        .values()
+        .try_fold(1u32, |power, min| power.checked_mul(*min))
+        .ok_or(GameError::Overflow)
+}
+
+pub fn part1(input: &str, bag: &Bag) -> Result<u32, GameError> {
+    let games = parse_games(input)?;
+    possible_games(&games, bag)?
+        .iter()
+        .try_fold(0u32, |sum, id| sum.checked_add(*id))
+        .ok_or(GameError::Overflow)
+}
+
+pub fn part2(input: &str, bag: &Bag) -> Result<u32, GameError> {
+    let games = parse_games(input)?;
+    let mut sum: u32 = 0;
+    for game in &games {
+        sum = sum
+            .checked_add(get_game_power(game, bag)?)
+            .ok_or(GameError::Overflow)?;
+    }
+    Ok(sum)
+}
+
+/// Whether each game is possible, its minimal bag and the rounds that do not
+/// fit in `bag`, followed by totals over every game.
+pub fn report(input: &str, bag: &Bag) -> Result<String, GameError> {
+    let games = parse_games(input)?;
+    let mut report = String::new();
+    for game in &games {
+        let violations = violations(game, bag)?;
+        if violations.is_empty() {
+            writeln!(report, "Game {}: possible", game.id).unwrap();
+        } else {
+            writeln!(report, "Game {}: impossible", game.id).unwrap();
+        }
+        writeln!(report, "  minimal bag: {}", format_bag(&game_bag(game))).unwrap();
+        for violation in violations {
+            writeln!(
+                report,
+                "  round {}: {} {} but the bag holds {}",
+                violation.round, violation.amount, violation.color, violation.available
+            )
+            .unwrap();
+        }
+    }
+    let possible = possible_games(&games, bag)?;
+    writeln!(report, "possible games: {}/{}", possible.len(), games.len()).unwrap();
+    writeln!(
+        report,
+        "minimal bag for all games: {}",
+        format_bag(&minimal_bag(&games))
+    )
+    .unwrap();
+    Ok(report)
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn part1(&self, input: &str) -> Result<String, Error> {
+        Ok(part1(input, &default_bag())?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, Error> {
+        Ok(part2(input, &default_bag())?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../data/day2/examples/part1.txt");
+
+    #[test]
+    fn example_with_default_bag() {
+        let bag = default_bag();
+        assert_eq!(part1(EXAMPLE, &bag), Ok(8));
+        assert_eq!(part2(EXAMPLE, &bag), Ok(2286));
+    }
+
+    #[test]
+    fn crlf_and_unterminated_input() {
+        let bag = default_bag();
+        for input in [
+            EXAMPLE.replace('\n', "\r\n"),
+            EXAMPLE.replace('\n', " \n"),
+            EXAMPLE.trim_end().to_string(),
+        ] {
+            assert_eq!(part1(&input, &bag), Ok(8));
+            assert_eq!(part2(&input, &bag), Ok(2286));
+        }
+    }
+
+    #[test]
+    fn extra_colours() {
+        let bag = parse_bag("1 red, 1 green, 1 blue, 2 yellow").unwrap();
+        let input = "Game 1: 1 red, 2 yellow\nGame 2: 3 yellow";
+        assert_eq!(part1(input, &bag), Ok(1));
+        assert_eq!(part2(input, &bag), Ok(0));
+    }
+
+    #[test]
+    fn bag_queries() {
+        let games = parse_games(EXAMPLE).unwrap();
+        assert_eq!(
+            format_bag(&minimal_bag(&games)),
+            "15 blue, 13 green, 20 red"
+        );
+        assert_eq!(possible_games(&games, &default_bag()), Ok(vec![1, 2, 5]));
+        assert_eq!(
+            violations(&games[2], &default_bag()),
+            Ok(vec![Violation {
+                round: 1,
+                color: "red".to_string(),
+                amount: 20,
+                available: 12
+            }])
+        );
+    }
+
+    #[test]
+    fn parse_errors_are_reported() {
+        let err = part1("Game 1: 1 red\nGame 2: red", &default_bag()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "parse error at line 2, column 1: unexpected trailing input"
+        );
+    }
+
+    #[test]
+    fn unknown_colour_is_an_error() {
+        let input = "Game 1: 1 red, 2 purple";
+        assert_eq!(
+            part1(input, &default_bag()),
+            Err(GameError::UnknownColor {
+                game: 1,
+                color: "purple".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn report_of_the_example() {
+        let report = report(EXAMPLE, &default_bag()).unwrap();
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(
+            lines[..2],
+            ["Game 1: possible", "  minimal bag: 6 blue, 2 green, 4 red"]
+        );
+        assert!(report.contains(
+            "Game 3: impossible\n  minimal bag: 6 blue, 13 green, 20 red\n  round 1: 20 red but the bag holds 12\n"
+        ));
+        assert!(report.ends_with(
+            "possible games: 3/5\nminimal bag for all games: 15 blue, 13 green, 20 red\n"
+        ));
+    }
+
+    #[test]
+    fn prints_the_example() {
+        let games = parse_games(EXAMPLE).unwrap();
+        let lines: Vec<String> = games.iter().map(Game::to_string).collect();
+        assert_eq!(lines, EXAMPLE.lines().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn overflowing_answers() {
+        let bag = default_bag();
+        let input = "Game 1: 70000 red, 70000 green, 1 blue\n";
+        assert_eq!(part2(input, &bag), Err(GameError::Overflow));
+        let input = "Game 4294967295: 1 red\nGame 1: 1 red\n";
+        assert_eq!(part1(input, &bag), Err(GameError::Overflow));
+    }
+
+    proptest! {
+        #[test]
+        fn games_round_trip(
+            games in prop::collection::vec(
+                (
+                    any::<u32>(),
+                    prop::collection::vec(
+                        prop::collection::vec((any::<u32>(), "[a-z]{1,8}"), 1..4),
+                        1..5,
+                    ),
+                ),
+                1..6,
+            )
+        ) {
+            let games: Vec<Game> = games
+                .iter()
+                .map(|(id, rounds)| Game {
+                    id: *id,
+                    rounds: rounds
+                        .iter()
+                        .map(|round| {
+                            round
+                                .iter()
+                                .map(|(amount, color)| Cube { color, amount: *amount })
+                                .collect()
+                        })
+                        .collect(),
+                })
+                .collect();
+            let text: Vec<String> = games.iter().map(Game::to_string).collect();
+            let text = text.join("\n");
+            prop_assert_eq!(parse_games(&text).unwrap(), games);
+        }
+    }
+}
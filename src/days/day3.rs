@@ -0,0 +1,464 @@
+use std::fmt::{self, Write};
+
+use clap::ValueEnum;
+
+use super::{Error, Solution};
+
+#[derive(Debug, PartialEq)]
+pub enum SchematicError {
+    /// A part number that does not fit in a `u32`.
+    Number {
+        row: usize,
+        text: String,
+    },
+    Overflow,
+}
+
+impl fmt::Display for SchematicError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SchematicError::Number { row, text } => {
+                write!(f, "part number {} on line {} is too large", text, row + 1)
+            }
+            SchematicError::Overflow => write!(f, "the sum of the part numbers overflows"),
+        }
+    }
+}
+
+impl std::error::Error for SchematicError {}
+
+#[derive(Debug, PartialEq)]
+pub struct PartNumber {
+    pub number: u32,
+    /// Digits exactly as written, leading zeros included.
+    pub text: String,
+    pub row: usize,
+    /// First column of the number.
+    pub start: usize,
+    /// Column just past the last digit.
+    pub end: usize,
+}
+
+impl PartNumber {
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (self.start..self.end).map(|column| (self.row, column))
+    }
+}
+
+pub struct Symbol {
+    pub symbol: char,
+    pub position: (usize, usize),
+}
+
+/// How the numbers around a gear are combined into its ratio.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Aggregation {
+    Product,
+    Sum,
+    Min,
+    Max,
+}
+
+impl Aggregation {
+    /// The combined values, or `None` if they overflow.
+    pub fn apply(&self, values: &[u32]) -> Option<u32> {
+        match self {
+            Aggregation::Product => values
+                .iter()
+                .try_fold(1u32, |product, value| product.checked_mul(*value)),
+            Aggregation::Sum => values
+                .iter()
+                .try_fold(0u32, |sum, value| sum.checked_add(*value)),
+            Aggregation::Min => Some(values.iter().copied().min().unwrap_or(0)),
+            Aggregation::Max => Some(values.iter().copied().max().unwrap_or(0)),
+        }
+    }
+}
+
+/// Which symbols are gears and how their ratio is computed.
+pub struct GearRule {
+    pub symbols: Vec<char>,
+    pub neighbors: usize,
+    pub aggregation: Aggregation,
+}
+
+impl Default for GearRule {
+    fn default() -> Self {
+        Self {
+            symbols: vec!['*'],
+            neighbors: 2,
+            aggregation: Aggregation::Product,
+        }
+    }
+}
+
+/// Engine schematic with every number and symbol indexed once, so that
+/// adjacency queries only look at the cells around the item.
+pub struct Schematic {
+    pub numbers: Vec<PartNumber>,
+    pub symbols: Vec<Symbol>,
+    /// Index into `numbers` of the number occupying each cell.
+    pub number_at: Vec<Vec<Option<usize>>>,
+    /// Index into `symbols` of the symbol occupying each cell.
+    pub symbol_at: Vec<Vec<Option<usize>>>,
+}
+
+impl Schematic {
+    pub fn parse(input: &str) -> Result<Self, SchematicError> {
+        let lines: Vec<Vec<char>> = input
+            .lines()
+            .map(|line| line.trim_end().chars().collect::<Vec<_>>())
+            .collect();
+
+        let mut number_at: Vec<Vec<Option<usize>>> =
+            lines.iter().map(|line| vec![None; line.len()]).collect();
+        let mut symbol_at = number_at.clone();
+
+        let mut numbers: Vec<PartNumber> = vec![];
+        for (line_index, line) in lines.iter().enumerate() {
+            numbers.extend(extract_numbers(line, line_index)?);
+        }
+        for (index, number) in numbers.iter().enumerate() {
+            for (i, j) in number.positions() {
+                number_at[i][j] = Some(index);
+            }
+        }
+
+        let mut symbols = vec![];
+        for (i, line) in lines.iter().enumerate() {
+            for (j, c) in line.iter().enumerate() {
+                if !(c.is_ascii_digit() || *c == '.') {
+                    symbol_at[i][j] = Some(symbols.len());
+                    symbols.push(Symbol {
+                        symbol: *c,
+                        position: (i, j),
+                    });
+                }
+            }
+        }
+
+        Ok(Self {
+            numbers,
+            symbols,
+            number_at,
+            symbol_at,
+        })
+    }
+
+    /// Cells around `(x, y)`, clipped to the schematic.
+    pub fn neighbors(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let mut neighbors = vec![];
+        for i in x.saturating_sub(1)..=x + 1 {
+            let Some(row) = self.number_at.get(i) else {
+                continue;
+            };
+            for j in y.saturating_sub(1)..=y + 1 {
+                if (i != x || j != y) && j < row.len() {
+                    neighbors.push((i, j));
+                }
+            }
+        }
+        neighbors
+    }
+
+    /// Indices of the numbers adjacent to the given symbol.
+    pub fn numbers_adjacent_to(&self, symbol: usize) -> Vec<usize> {
+        let (x, y) = self.symbols[symbol].position;
+        let mut numbers: Vec<usize> = self
+            .neighbors(x, y)
+            .iter()
+            .filter_map(|(i, j)| self.number_at[*i][*j])
+            .collect();
+        numbers.sort();
+        numbers.dedup();
+        numbers
+    }
+
+    /// Indices of the symbols adjacent to the given number.
+    pub fn symbols_adjacent_to(&self, number: usize) -> Vec<usize> {
+        let mut symbols: Vec<usize> = self.numbers[number]
+            .positions()
+            .flat_map(|(x, y)| self.neighbors(x, y))
+            .filter_map(|(i, j)| self.symbol_at[i][j])
+            .collect();
+        symbols.sort();
+        symbols.dedup();
+        symbols
+    }
+
+    /// Whether the given number is adjacent to a symbol.
+    pub fn is_part_number(&self, number: usize) -> bool {
+        !self.symbols_adjacent_to(number).is_empty()
+    }
+}
+
+pub fn extract_numbers(
+    line: &[char],
+    line_index: usize,
+) -> Result<Vec<PartNumber>, SchematicError> {
+    let mut numbers: Vec<PartNumber> = vec![];
+    let mut start: Option<usize> = None;
+
+    for i in 0..=line.len() {
+        let is_digit = line.get(i).is_some_and(|c| c.is_ascii_digit());
+        match (start, is_digit) {
+            (None, true) => start = Some(i),
+            (Some(first), false) => {
+                let text: String = line[first..i].iter().collect();
+                let number = text.parse().map_err(|_| SchematicError::Number {
+                    row: line_index,
+                    text: text.clone(),
+                })?;
+                numbers.push(PartNumber {
+                    number,
+                    text,
+                    row: line_index,
+                    start: first,
+                    end: i,
+                });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(numbers)
+}
+
+pub fn part1(schematic: &Schematic) -> Result<u32, SchematicError> {
+    (0..schematic.numbers.len())
+        .filter(|number| schematic.is_part_number(*number))
+        .map(|number| schematic.numbers[number].number)
+        .try_fold(0u32, |sum, number| sum.checked_add(number))
+        .ok_or(SchematicError::Overflow)
+}
+
+/// Indices of the symbols that are gears under `rule`, each with the indices
+/// of its numbers.
+pub fn gears(schematic: &Schematic, rule: &GearRule) -> Vec<(usize, Vec<usize>)> {
+    (0..schematic.symbols.len())
+        .filter(|symbol| rule.symbols.contains(&schematic.symbols[*symbol].symbol))
+        .map(|symbol| (symbol, schematic.numbers_adjacent_to(symbol)))
+        .filter(|(_, numbers)| numbers.len() == rule.neighbors)
+        .collect()
+}
+
+pub fn part2(schematic: &Schematic, rule: &GearRule) -> Result<u32, SchematicError> {
+    gears(schematic, rule)
+        .into_iter()
+        .map(|(_, numbers)| {
+            let values: Vec<u32> = numbers
+                .iter()
+                .map(|number| schematic.numbers[*number].number)
+                .collect();
+            rule.aggregation.apply(&values)
+        })
+        .try_fold(0u32, |sum, ratio| sum.checked_add(ratio?))
+        .ok_or(SchematicError::Overflow)
+}
+
+/// Every symbol with its position and the numbers adjacent to it, one per
+/// line.
+pub fn list_symbols(schematic: &Schematic) -> String {
+    let mut list = String::new();
+    for (index, symbol) in schematic.symbols.iter().enumerate() {
+        let numbers: Vec<String> = schematic
+            .numbers_adjacent_to(index)
+            .iter()
+            .map(|number| schematic.numbers[*number].number.to_string())
+            .collect();
+        writeln!(
+            list,
+            "'{}' at ({}, {}): {}",
+            symbol.symbol,
+            symbol.position.0,
+            symbol.position.1,
+            numbers.join(", ")
+        )
+        .unwrap();
+    }
+    list
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn part1(&self, input: &str) -> Result<String, Error> {
+        Ok(part1(&Schematic::parse(input)?)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, Error> {
+        Ok(part2(&Schematic::parse(input)?, &GearRule::default())?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../data/day3/examples/part1.txt");
+
+    #[test]
+    fn example() {
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&schematic), Ok(4361));
+        assert_eq!(part2(&schematic, &GearRule::default()), Ok(467835));
+    }
+
+    #[test]
+    fn symbols_of_the_example() {
+        let list = list_symbols(&Schematic::parse(EXAMPLE).unwrap());
+        assert!(list.starts_with("'*' at (1, 3): 467, 35\n'#' at (3, 6): 633\n"));
+        assert_eq!(list.lines().count(), 6);
+    }
+
+    #[test]
+    fn crlf_and_unterminated_input() {
+        for input in [
+            EXAMPLE.replace('\n', "\r\n"),
+            EXAMPLE.replace('\n', " \n"),
+            EXAMPLE.trim_end().to_string(),
+        ] {
+            let schematic = Schematic::parse(&input).unwrap();
+            assert_eq!(part1(&schematic), Ok(4361));
+            assert_eq!(part2(&schematic, &GearRule::default()), Ok(467835));
+        }
+    }
+
+    #[test]
+    fn custom_gear_rule() {
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        let rule = GearRule {
+            symbols: vec!['*', '#', '+'],
+            neighbors: 1,
+            aggregation: Aggregation::Max,
+        };
+        // 617 next to '*', 633 next to '#', 592 next to '+'
+        assert_eq!(part2(&schematic, &rule), Ok(617 + 633 + 592));
+
+        let rule = GearRule {
+            aggregation: Aggregation::Sum,
+            ..GearRule::default()
+        };
+        assert_eq!(part2(&schematic, &rule), Ok(467 + 35 + 755 + 598));
+    }
+
+    fn extract(line: &str) -> Vec<PartNumber> {
+        let line: Vec<char> = line.chars().collect();
+        extract_numbers(&line, 0).unwrap()
+    }
+
+    fn number(number: u32, text: &str, start: usize, end: usize) -> PartNumber {
+        PartNumber {
+            number,
+            text: text.to_string(),
+            row: 0,
+            start,
+            end,
+        }
+    }
+
+    #[test]
+    fn extract_zero() {
+        assert_eq!(extract("0"), vec![number(0, "0", 0, 1)]);
+        assert_eq!(
+            extract(".0.10."),
+            vec![number(0, "0", 1, 2), number(10, "10", 3, 5)]
+        );
+    }
+
+    #[test]
+    fn extract_leading_zeros() {
+        assert_eq!(extract("..007*"), vec![number(7, "007", 2, 5)]);
+        assert_eq!(
+            extract("00.000"),
+            vec![number(0, "00", 0, 2), number(0, "000", 3, 6)]
+        );
+    }
+
+    #[test]
+    fn extract_at_line_edges() {
+        assert_eq!(
+            extract("12...34"),
+            vec![number(12, "12", 0, 2), number(34, "34", 5, 7)]
+        );
+        assert_eq!(extract("......"), vec![]);
+        assert_eq!(extract(""), vec![]);
+    }
+
+    #[test]
+    fn extract_numbers_touching_symbols() {
+        assert_eq!(
+            extract("1*2#3"),
+            vec![
+                number(1, "1", 0, 1),
+                number(2, "2", 2, 3),
+                number(3, "3", 4, 5)
+            ]
+        );
+    }
+
+    #[test]
+    fn zero_part_numbers() {
+        let schematic = Schematic::parse("0*5\n...").unwrap();
+        assert_eq!(part1(&schematic), Ok(5));
+        assert_eq!(part2(&schematic, &GearRule::default()), Ok(0));
+
+        let schematic = Schematic::parse("007\n..*\n3..").unwrap();
+        assert_eq!(part1(&schematic), Ok(7));
+        assert_eq!(schematic.numbers[0].text, "007");
+    }
+
+    #[test]
+    fn numbers_at_grid_edges() {
+        let schematic = Schematic::parse("9....\n*...8\n#...#\n1...*\n.2..4").unwrap();
+        // The 2 only touches the 1, which is not a symbol.
+        assert_eq!(part1(&schematic), Ok(9 + 8 + 1 + 4));
+        assert_eq!(part2(&schematic, &GearRule::default()), Ok(0));
+    }
+
+    #[test]
+    fn numbers_touching_each_other() {
+        // Vertically and diagonally touching numbers are distinct and are not
+        // part numbers without a symbol.
+        let schematic = Schematic::parse("12.\n.34\n5..").unwrap();
+        assert_eq!(schematic.numbers.len(), 3);
+        assert_eq!(part1(&schematic), Ok(0));
+
+        // A gear touching the same number twice still sees one neighbour.
+        let schematic = Schematic::parse("123\n.*.\n4.5").unwrap();
+        assert_eq!(
+            schematic
+                .numbers_adjacent_to(0)
+                .iter()
+                .map(|number| schematic.numbers[*number].number)
+                .collect::<Vec<_>>(),
+            vec![123, 4, 5]
+        );
+        assert_eq!(part2(&schematic, &GearRule::default()), Ok(0));
+    }
+
+    #[test]
+    fn gear_in_first_row() {
+        let schematic = Schematic::parse("2*3\n...").unwrap();
+        assert_eq!(part1(&schematic), Ok(5));
+        assert_eq!(part2(&schematic, &GearRule::default()), Ok(6));
+    }
+
+    #[test]
+    fn huge_part_numbers() {
+        assert_eq!(
+            Schematic::parse("1.\n.*99999999999\n").err(),
+            Some(SchematicError::Number {
+                row: 1,
+                text: "99999999999".to_string()
+            })
+        );
+        let schematic = Schematic::parse("4000000000*4000000000\n").unwrap();
+        assert_eq!(part1(&schematic), Err(SchematicError::Overflow));
+        assert_eq!(
+            part2(&schematic, &GearRule::default()),
+            Err(SchematicError::Overflow)
+        );
+    }
+}
@@ -0,0 +1,643 @@
+use std::fmt::{self, Write};
+use std::ops;
+use std::str::FromStr;
+
+use super::{no_such_part, Error, Lines, Solution};
+use crate::parse::{self, ParseError};
+use clap::ValueEnum;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{self, space1},
+    combinator::{map_opt, verify},
+    sequence::{delimited, preceded, separated_pair, tuple},
+    IResult,
+};
+
+/// Set of card numbers, all of which are below 128, that remembers the
+/// order they were inserted in.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NumberSet {
+    bits: u128,
+    numbers: Vec<u32>,
+}
+
+impl NumberSet {
+    /// Adds `number` and returns whether it was not in the set yet.
+    pub fn insert(&mut self, number: u32) -> bool {
+        if self.bits & (1 << number) != 0 {
+            return false;
+        }
+        self.bits |= 1 << number;
+        self.numbers.push(number);
+        true
+    }
+
+    pub fn intersection_len(&self, other: &NumberSet) -> usize {
+        (self.bits & other.bits).count_ones() as usize
+    }
+
+    /// The numbers in the order they were inserted.
+    pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.numbers.iter().copied()
+    }
+}
+
+/// Numbers right-aligned on two columns, as in the puzzle input.
+impl fmt::Display for NumberSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, number) in self.iter().enumerate() {
+            if index > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{:>2}", number)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromIterator<u32> for NumberSet {
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+        let mut set = NumberSet::default();
+        for number in iter {
+            set.insert(number);
+        }
+        set
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Card {
+    pub id: u32,
+    pub winning: NumberSet,
+    pub owned: NumberSet,
+}
+
+impl Card {
+    pub fn matches(&self) -> usize {
+        self.winning.intersection_len(&self.owned)
+    }
+}
+
+/// The width, if any, right-aligns the card id, so that `{:3}` writes
+/// `Card   1: ...` as in the puzzle input. Numbers come out in the order
+/// they were read.
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Card {:>width$}: {} | {}",
+            self.id,
+            self.winning,
+            self.owned,
+            width = f.width().unwrap_or(0)
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CardError {
+    Parse(ParseError),
+    /// Card ids must be 1, 2, 3, ... since copies are won by position.
+    OutOfOrder {
+        line: usize,
+        expected: u32,
+        found: u32,
+    },
+}
+
+impl fmt::Display for CardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CardError::Parse(err) => write!(f, "{}", err),
+            CardError::OutOfOrder {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {} holds card {} but card {} was expected",
+                line, found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CardError {}
+
+/// What to do when a card wins copies of cards past the end of the table.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum OverflowPolicy {
+    /// Ignore the copies that would land past the last card.
+    Clamp,
+    /// Stop the simulation with an error.
+    Error,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Overflow {
+    pub card: usize,
+    pub matches: usize,
+    pub cards: usize,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "card {} wins {} cards but only {} cards follow it",
+            self.card + 1,
+            self.matches,
+            self.cards - self.card - 1
+        )
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+#[derive(Debug, PartialEq)]
+pub enum SimulationError {
+    Overflow(Overflow),
+    /// A card, or all of them together, with more than `u64::MAX` copies.
+    TooManyCopies {
+        card: usize,
+    },
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SimulationError::Overflow(overflow) => write!(f, "{}", overflow),
+            SimulationError::TooManyCopies { card } => {
+                write!(f, "the copies up to card {} do not fit in a u64", card + 1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SimulationError {}
+
+/// How many points a card with a given number of matches is worth.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Scoring {
+    /// 1 point for the first match, doubled for each further one.
+    Doubling,
+    /// 1 point per match.
+    Linear,
+    /// 1, 2, 3, 5, 8, ... points for 1, 2, 3, 4, 5, ... matches.
+    Fibonacci,
+    /// Points for 1, 2, 3, ... matches, read from the table.
+    Table(Vec<u64>),
+}
+
+impl Scoring {
+    /// Points for `n` matches, or `None` if they do not fit in a `u64` or
+    /// the table has no entry for `n`.
+    pub fn points(&self, n: usize) -> Option<u64> {
+        if n == 0 {
+            return Some(0);
+        }
+        match self {
+            Scoring::Doubling => 2u64.checked_pow(u32::try_from(n - 1).ok()?),
+            Scoring::Linear => u64::try_from(n).ok(),
+            Scoring::Fibonacci => {
+                let (mut a, mut b): (u64, u64) = (1, 1);
+                for _ in 1..n {
+                    (a, b) = (b, a.checked_add(b)?);
+                }
+                Some(b)
+            }
+            Scoring::Table(table) => table.get(n - 1).copied(),
+        }
+    }
+}
+
+impl FromStr for Scoring {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "doubling" => Ok(Scoring::Doubling),
+            "linear" => Ok(Scoring::Linear),
+            "fibonacci" => Ok(Scoring::Fibonacci),
+            _ => match s.strip_prefix("table:") {
+                Some(table) => table
+                    .split(',')
+                    .map(|value| value.trim().parse::<u64>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(Scoring::Table)
+                    .map_err(|err| format!("invalid scoring table '{}': {}", table, err)),
+                None => Err(format!(
+                    "unknown scoring '{}', expected doubling, linear, fibonacci or table:<points,...>",
+                    s
+                )),
+            },
+        }
+    }
+}
+
+pub fn game(input: &str) -> IResult<&str, u32> {
+    preceded(tuple((tag("Card"), space1)), complete::u32)(input)
+}
+
+/// Numbers up to the end of the line or the `|` between the two sets, none
+/// of them repeated.
+pub fn number_set(input: &str) -> IResult<&str, NumberSet> {
+    let mut set = NumberSet::default();
+    let number = map_opt(verify(complete::u32, |n| *n < 128), |n| {
+        set.insert(n).then_some(n)
+    });
+    let (res, _) = parse::list_until(number, tag("|"))(input)?;
+    Ok((res, set))
+}
+
+pub fn parse_card(line: &str) -> IResult<&str, Card> {
+    let (res, (id, (winning, owned))) = parse::labelled(
+        game,
+        separated_pair(number_set, delimited(space1, tag("|"), space1), number_set),
+    )(line)?;
+    Ok((res, Card { id, winning, owned }))
+}
+
+pub fn parse_cards(input: &str) -> Result<Vec<Card>, CardError> {
+    let cards = parse::parse_all(parse::lines(parse_card), input).map_err(CardError::Parse)?;
+    for (index, card) in cards.iter().enumerate() {
+        let expected = index as u32 + 1;
+        if card.id != expected {
+            return Err(CardError::OutOfOrder {
+                line: index + 1,
+                expected,
+                found: card.id,
+            });
+        }
+    }
+    Ok(cards)
+}
+
+/// Number of matches of each card, parsing `lines` one at a time.
+pub fn stream_matches(lines: Lines) -> Result<Vec<usize>, Error> {
+    let mut matches = vec![];
+    for (index, line) in lines.enumerate() {
+        let card = parse::parse_all(parse_card, &line?).map_err(|err| {
+            CardError::Parse(ParseError {
+                line: index + 1,
+                ..err
+            })
+        })?;
+        let expected = index as u32 + 1;
+        if card.id != expected {
+            return Err(CardError::OutOfOrder {
+                line: index + 1,
+                expected,
+                found: card.id,
+            }
+            .into());
+        }
+        matches.push(card.matches());
+    }
+    Ok(matches)
+}
+
+/// Total points of all cards, or `None` if the total does not fit in a
+/// `u64` or a card has no score under the scheme.
+pub fn points(matches: &[usize], scoring: &Scoring) -> Option<u64> {
+    matches
+        .iter()
+        .try_fold(0u64, |sum, n| sum.checked_add(scoring.points(*n)?))
+}
+
+/// Copies of each card while the wins are processed one card at a time.
+pub struct Cascade {
+    pub matches: Vec<usize>,
+    pub policy: OverflowPolicy,
+    pub copies: Vec<u64>,
+    /// Next card to process.
+    pub card: usize,
+}
+
+impl Cascade {
+    pub fn new(matches: Vec<usize>, policy: OverflowPolicy) -> Self {
+        Self {
+            copies: vec![1; matches.len()],
+            matches,
+            policy,
+            card: 0,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.card >= self.matches.len()
+    }
+
+    /// Gives the copies of the next card to the cards it wins, and returns
+    /// the indices of those cards.
+    pub fn step(&mut self) -> Result<ops::Range<usize>, SimulationError> {
+        let index = self.card;
+        let Some(n) = self.matches.get(index) else {
+            return Ok(index..index);
+        };
+        let last = index + n;
+        if last >= self.matches.len() && self.policy == OverflowPolicy::Error {
+            return Err(SimulationError::Overflow(Overflow {
+                card: index,
+                matches: *n,
+                cards: self.matches.len(),
+            }));
+        }
+        let won = index + 1..last.min(self.matches.len() - 1) + 1;
+        for i in won.clone() {
+            self.copies[i] = self.copies[i]
+                .checked_add(self.copies[index])
+                .ok_or(SimulationError::TooManyCopies { card: i })?;
+        }
+        self.card += 1;
+        Ok(won)
+    }
+}
+
+/// Number of copies of each card once every win has been processed.
+pub fn simulate(matches: &[usize], policy: OverflowPolicy) -> Result<Vec<u64>, SimulationError> {
+    let mut cascade = Cascade::new(matches.to_vec(), policy);
+    while !cascade.is_done() {
+        cascade.step()?;
+    }
+    Ok(cascade.copies)
+}
+
+/// Total number of cards once every win has been processed.
+pub fn total_copies(copies: &[u64]) -> Result<u64, SimulationError> {
+    let mut total: u64 = 0;
+    for (card, count) in copies.iter().enumerate() {
+        total = total
+            .checked_add(*count)
+            .ok_or(SimulationError::TooManyCopies { card })?;
+    }
+    Ok(total)
+}
+
+/// How the copies propagate, one line per card.
+pub fn format_cascade(matches: &[usize], copies: &[u64]) -> String {
+    let mut cascade = String::new();
+    for (index, (n, count)) in matches.iter().zip(copies.iter()).enumerate() {
+        let last = (index + n).min(matches.len() - 1);
+        if *n == 0 || last == index {
+            writeln!(
+                cascade,
+                "Card {}: {} copies, {} matches",
+                index + 1,
+                count,
+                n
+            )
+            .unwrap();
+        } else {
+            writeln!(
+                cascade,
+                "Card {}: {} copies, {} matches -> +{} to cards {}..={}",
+                index + 1,
+                count,
+                n,
+                count,
+                index + 2,
+                last + 1
+            )
+            .unwrap();
+        }
+    }
+    cascade
+}
+
+pub struct Day4;
+
+fn answer(part: u8, matches: &[usize]) -> Result<String, Error> {
+    match part {
+        1 => Ok(points(matches, &Scoring::Doubling)
+            .ok_or("points do not fit in a u64")?
+            .to_string()),
+        2 => Ok(total_copies(&simulate(matches, OverflowPolicy::Clamp)?)?.to_string()),
+        _ => Err(no_such_part(part)),
+    }
+}
+
+impl Solution for Day4 {
+    fn part1(&self, input: &str) -> Result<String, Error> {
+        let matches: Vec<usize> = parse_cards(input)?.iter().map(Card::matches).collect();
+        answer(1, &matches)
+    }
+
+    fn part2(&self, input: &str) -> Result<String, Error> {
+        let matches: Vec<usize> = parse_cards(input)?.iter().map(Card::matches).collect();
+        answer(2, &matches)
+    }
+
+    fn solve_lines(&self, part: u8, lines: Lines) -> Result<String, Error> {
+        answer(part, &stream_matches(lines)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    const EXAMPLE: &str = include_str!("../data/day4/examples/part1.txt");
+
+    fn example_matches() -> Vec<usize> {
+        parse_cards(EXAMPLE)
+            .unwrap()
+            .iter()
+            .map(Card::matches)
+            .collect()
+    }
+
+    #[test]
+    fn crlf_and_unterminated_input() {
+        for input in [
+            EXAMPLE.replace('\n', "\r\n"),
+            EXAMPLE.replace('\n', " \n"),
+            EXAMPLE.trim_end().to_string(),
+        ] {
+            let cards = parse_cards(&input).unwrap();
+            let matches: Vec<usize> = cards.iter().map(Card::matches).collect();
+            assert_eq!(matches, example_matches());
+        }
+    }
+
+    #[test]
+    fn parse_example_card() {
+        let (_, card) = parse_card("Card  12:  1 21 | 21  5 127").unwrap();
+        assert_eq!(card.id, 12);
+        assert_eq!(card.winning, [1, 21].into_iter().collect());
+        assert_eq!(card.owned, [21, 5, 127].into_iter().collect());
+        assert_eq!(card.matches(), 1);
+        assert!(parse_card("Card 1: 128 | 1").is_err());
+        assert_eq!(
+            parse_cards("Card 1: 5 1 1 | 1\n"),
+            Err(CardError::Parse(ParseError {
+                line: 1,
+                column: 13,
+                kind: nom::error::ErrorKind::MapOpt
+            }))
+        );
+        assert!(parse_cards("Card 1: 1 | 1 1\n").is_err());
+    }
+
+    #[test]
+    fn card_ids_must_be_contiguous() {
+        assert_eq!(
+            parse_cards("Card 1: 1 | 1\nCard 3: 1 | 1"),
+            Err(CardError::OutOfOrder {
+                line: 2,
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            parse_cards("Card 2: 1 | 1"),
+            Err(CardError::OutOfOrder {
+                line: 1,
+                expected: 1,
+                found: 2
+            })
+        );
+        assert_eq!(
+            parse_cards("Card 1: 1 | x").unwrap_err().to_string(),
+            "parse error at line 1, column 13: expected Digit"
+        );
+    }
+
+    #[test]
+    fn streamed_lines() {
+        let stream = |input: &str| {
+            stream_matches(&mut crate::utils::Lines::new(input.as_bytes()))
+                .map_err(|err| err.to_string())
+        };
+        assert_eq!(stream(EXAMPLE), Ok(example_matches()));
+        assert_eq!(
+            Day4.solve_lines(2, &mut crate::utils::Lines::new(EXAMPLE.as_bytes()))
+                .unwrap(),
+            "30"
+        );
+        assert_eq!(
+            stream("Card 1: 1 | 1\nCard 2: 1 | x"),
+            Err("parse error at line 2, column 13: expected Digit".to_string())
+        );
+        assert_eq!(
+            stream("Card 1: 1 | 1\nCard 3: 1 | 1"),
+            Err("line 2 holds card 3 but card 2 was expected".to_string())
+        );
+    }
+
+    #[test]
+    fn example_copies() {
+        let copies = simulate(&example_matches(), OverflowPolicy::Error).unwrap();
+        assert_eq!(copies, vec![1, 2, 4, 8, 14, 1]);
+        assert_eq!(copies.iter().sum::<u64>(), 30);
+        let cascade = format_cascade(&example_matches(), &copies);
+        assert!(cascade.starts_with("Card 1: 1 copies, 4 matches -> +1 to cards 2..=5\n"));
+        assert!(cascade.ends_with("Card 6: 1 copies, 0 matches\n"));
+    }
+
+    #[test]
+    fn scoring_schemes() {
+        let matches = example_matches();
+        assert_eq!(points(&matches, &Scoring::Doubling), Some(13));
+        assert_eq!(points(&matches, &Scoring::Linear), Some(4 + 2 + 2 + 1));
+        assert_eq!(points(&matches, &Scoring::Fibonacci), Some(5 + 2 + 2 + 1));
+        assert_eq!(
+            points(&matches, &"table:10, 20, 30, 40".parse().unwrap()),
+            Some(40 + 20 + 20 + 10)
+        );
+        assert_eq!(points(&matches, &Scoring::Table(vec![1, 2])), None);
+    }
+
+    #[test]
+    fn scoring_overflow() {
+        assert_eq!(Scoring::Doubling.points(64), Some(1 << 63));
+        assert_eq!(Scoring::Doubling.points(65), None);
+        assert_eq!(Scoring::Fibonacci.points(92), Some(12200160415121876738));
+        assert_eq!(Scoring::Fibonacci.points(93), None);
+        assert_eq!(points(&[64, 64], &Scoring::Doubling), None);
+    }
+
+    #[test]
+    fn overflow_policies() {
+        let matches = vec![1, 3, 0];
+        assert_eq!(simulate(&matches, OverflowPolicy::Clamp), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            simulate(&matches, OverflowPolicy::Error),
+            Err(SimulationError::Overflow(Overflow {
+                card: 1,
+                matches: 3,
+                cards: 3
+            }))
+        );
+    }
+
+    #[test]
+    fn too_many_copies() {
+        // Each card wins a copy of the next two, so the copies grow like the
+        // Fibonacci numbers.
+        let mut matches = vec![2; 100];
+        matches[98] = 1;
+        matches[99] = 0;
+        assert_eq!(
+            simulate(&matches, OverflowPolicy::Error),
+            Err(SimulationError::TooManyCopies { card: 91 })
+        );
+        let copies = simulate(&vec![2; 90], OverflowPolicy::Clamp).unwrap();
+        assert_eq!(
+            total_copies(&copies),
+            Err(SimulationError::TooManyCopies { card: 89 })
+        );
+    }
+
+    #[test]
+    fn prints_cards() {
+        let (_, card) = parse_card("Card  12:  1 21 | 21  5 127").unwrap();
+        assert_eq!(card.to_string(), "Card 12:  1 21 | 21  5 127");
+        assert_eq!(format!("{:3}", card), "Card  12:  1 21 | 21  5 127");
+        assert_eq!(card.owned.iter().collect::<Vec<_>>(), [21, 5, 127]);
+    }
+
+    proptest! {
+        #[test]
+        fn cards_round_trip(
+            cards in prop::collection::vec(
+                (
+                    prop::collection::btree_set(0..128u32, 1..10)
+                        .prop_map(Vec::from_iter)
+                        .prop_shuffle(),
+                    prop::collection::btree_set(0..128u32, 1..25)
+                        .prop_map(Vec::from_iter)
+                        .prop_shuffle(),
+                ),
+                1..8,
+            )
+        ) {
+            let text: Vec<String> = cards
+                .iter()
+                .enumerate()
+                .map(|(index, (winning, owned))| {
+                    let card = Card {
+                        id: index as u32 + 1,
+                        winning: winning.iter().copied().collect(),
+                        owned: owned.iter().copied().collect(),
+                    };
+                    format!("{:3}", card)
+                })
+                .collect();
+            let parsed = parse_cards(&text.join("\n")).unwrap();
+            prop_assert_eq!(parsed.len(), cards.len());
+            for (index, (card, (winning, owned))) in parsed.iter().zip(&cards).enumerate() {
+                prop_assert_eq!(card.id, index as u32 + 1);
+                prop_assert_eq!(&card.winning.iter().collect::<Vec<_>>(), winning);
+                prop_assert_eq!(&card.owned.iter().collect::<Vec<_>>(), owned);
+                let owned: BTreeSet<_> = owned.iter().collect();
+                prop_assert_eq!(
+                    card.matches(),
+                    winning.iter().filter(|number| owned.contains(number)).count()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,375 @@
+use std::fmt;
+
+use super::{Error, Solution};
+use crate::parse::{self, ParseError};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{self, space1},
+    combinator::map,
+    sequence::{pair, preceded, tuple},
+    IResult,
+};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Range {
+    pub dst: u64,
+    pub src: u64,
+    pub len: u64,
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.dst, self.src, self.len)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AlmanacError {
+    Parse(ParseError),
+    /// A range whose source or destination ends past `u64::MAX`.
+    RangeOverflow(Range),
+    /// Two ranges of the same map with overlapping sources.
+    Overlap(Range, Range),
+    /// Part 2 reads the seeds as pairs of start and length.
+    OddSeeds,
+    SeedOverflow {
+        start: u64,
+        len: u64,
+    },
+    /// No seed to plant, as when every seed range of part 2 is empty.
+    NoSeeds,
+}
+
+impl fmt::Display for AlmanacError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlmanacError::Parse(err) => write!(f, "{}", err),
+            AlmanacError::RangeOverflow(range) => {
+                write!(f, "range '{}' ends past {}", range, u64::MAX)
+            }
+            AlmanacError::Overlap(a, b) => write!(f, "ranges '{}' and '{}' overlap", a, b),
+            AlmanacError::OddSeeds => write!(f, "the seeds are not pairs of start and length"),
+            AlmanacError::SeedOverflow { start, len } => {
+                write!(f, "seed range {} {} ends past {}", start, len, u64::MAX)
+            }
+            AlmanacError::NoSeeds => write!(f, "there are no seeds to plant"),
+        }
+    }
+}
+
+impl std::error::Error for AlmanacError {}
+
+impl From<ParseError> for AlmanacError {
+    fn from(err: ParseError) -> Self {
+        AlmanacError::Parse(err)
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Range2 {
+    pub src: u64,
+    pub len: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct RangeMap {
+    pub ranges: Vec<Range>,
+}
+
+impl RangeMap {
+    pub fn get(&self, n: u64) -> u64 {
+        for range in self.ranges.iter() {
+            if (range.src..range.src + range.len).contains(&n) {
+                return range.dst + (n - range.src);
+            }
+        }
+        n
+    }
+
+    /// Map of `ranges`, which must not overlap nor end past `u64::MAX`.
+    pub fn new(ranges: &[Range]) -> Result<Self, AlmanacError> {
+        let mut r = vec![];
+        for range in ranges.iter() {
+            if range.src.checked_add(range.len).is_none()
+                || range.dst.checked_add(range.len).is_none()
+            {
+                return Err(AlmanacError::RangeOverflow(*range));
+            }
+            r.push(*range);
+        }
+        r.sort_by_key(|range| range.src);
+
+        let mut negative_ranges: Vec<Range> = vec![];
+        for pair in r.windows(2) {
+            let start = pair[0].src + pair[0].len;
+            let len = pair[1]
+                .src
+                .checked_sub(start)
+                .ok_or(AlmanacError::Overlap(pair[0], pair[1]))?;
+            negative_ranges.push(Range {
+                dst: start,
+                src: start,
+                len,
+            })
+        }
+        r.append(&mut negative_ranges);
+        r.sort_by_key(|range| range.src);
+        Ok(Self { ranges: r })
+    }
+}
+
+/// The almanac maps, in the order they are applied.
+pub const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+/// The seeds and the ranges of each of the [`MAPS`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<Vec<Range>>,
+}
+
+impl fmt::Display for Almanac {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "seeds:")?;
+        for seed in &self.seeds {
+            write!(f, " {}", seed)?;
+        }
+        for (name, ranges) in MAPS.iter().zip(&self.maps) {
+            write!(f, "\n\n{} map:", name)?;
+            for range in ranges {
+                write!(f, "\n{}", range)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn range(input: &str) -> IResult<&str, Range> {
+    map(
+        tuple((
+            complete::u64,
+            preceded(space1, complete::u64),
+            preceded(space1, complete::u64),
+        )),
+        |(dst, src, len)| Range { dst, src, len },
+    )(input)
+}
+
+pub fn parse(input: &str) -> IResult<&str, Almanac> {
+    let (mut res, (_, seeds)) = parse::labelled(tag("seeds"), parse::u64s)(input)?;
+
+    let mut maps = vec![];
+    for name in MAPS {
+        let (remaining, (_, ranges)) = preceded(
+            parse::blank_line,
+            parse::block(pair(tag(name), tag(" map")), range),
+        )(res)?;
+        maps.push(ranges);
+        res = remaining;
+    }
+
+    Ok((res, Almanac { seeds, maps }))
+}
+
+pub fn part1(input: String) -> Result<u64, AlmanacError> {
+    let Almanac {
+        seeds,
+        maps: values,
+    } = parse::parse_all(parse, &input)?;
+    let seed2soil = RangeMap::new(&values[0])?;
+    let soil2fertilizer = RangeMap::new(&values[1])?;
+    let fertilizer2water = RangeMap::new(&values[2])?;
+    let water2light = RangeMap::new(&values[3])?;
+    let light2temperature = RangeMap::new(&values[4])?;
+    let temperature2humidity = RangeMap::new(&values[5])?;
+    let humidity2location = RangeMap::new(&values[6])?;
+
+    seeds
+        .iter()
+        .map(|seed| {
+            let soil = seed2soil.get(*seed);
+            let fertilizer = soil2fertilizer.get(soil);
+            let water = fertilizer2water.get(fertilizer);
+            let light = water2light.get(water);
+            let temperature = light2temperature.get(light);
+            let humidity = temperature2humidity.get(temperature);
+            humidity2location.get(humidity)
+        })
+        .min()
+        .ok_or(AlmanacError::NoSeeds)
+}
+
+pub fn part2(input: String) -> Result<u64, AlmanacError> {
+    let Almanac {
+        seeds,
+        maps: values,
+    } = parse::parse_all(parse, &input)?;
+
+    if seeds.len() % 2 != 0 {
+        return Err(AlmanacError::OddSeeds);
+    }
+    let mut seed_ranges = vec![];
+    for seed_range in seeds.chunks(2) {
+        let (start, len) = (seed_range[0], seed_range[1]);
+        if start.checked_add(len).is_none() {
+            return Err(AlmanacError::SeedOverflow { start, len });
+        }
+        if len > 0 {
+            seed_ranges.push(Range2 { src: start, len });
+        }
+    }
+    let seed2soil = RangeMap::new(&values[0])?;
+    let soil2fertilizer = RangeMap::new(&values[1])?;
+    let fertilizer2water = RangeMap::new(&values[2])?;
+    let water2light = RangeMap::new(&values[3])?;
+    let light2temperature = RangeMap::new(&values[4])?;
+    let temperature2humidity = RangeMap::new(&values[5])?;
+    let humidity2location = RangeMap::new(&values[6])?;
+
+    seed_ranges
+        .into_par_iter()
+        .map(|range| {
+            let mut min = u64::MAX;
+            for seed in range.src..range.src + range.len {
+                let soil = seed2soil.get(seed);
+                let fertilizer = soil2fertilizer.get(soil);
+                let water = fertilizer2water.get(fertilizer);
+                let light = water2light.get(water);
+                let temperature = light2temperature.get(light);
+                let humidity = temperature2humidity.get(temperature);
+                let loc = humidity2location.get(humidity);
+
+                if loc < min {
+                    min = loc
+                }
+            }
+            min
+        })
+        .min()
+        .ok_or(AlmanacError::NoSeeds)
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    fn part1(&self, input: &str) -> Result<String, Error> {
+        Ok(part1(input.to_string())?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, Error> {
+        Ok(part2(input.to_string())?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../data/day5/examples/part1.txt");
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE.to_string()), Ok(35));
+        assert_eq!(part2(EXAMPLE.to_string()), Ok(46));
+    }
+
+    #[test]
+    fn crlf_and_unterminated_input() {
+        for input in [
+            EXAMPLE.replace('\n', "\r\n"),
+            EXAMPLE.replace('\n', " \n"),
+            EXAMPLE.trim_end().to_string(),
+        ] {
+            assert_eq!(part1(input.clone()), Ok(35));
+            assert_eq!(part2(input), Ok(46));
+        }
+    }
+
+    /// Up to eight ranges whose sources do not overlap.
+    fn disjoint_ranges() -> impl Strategy<Value = Vec<Range>> {
+        prop::collection::vec((0..1000u64, 1..1000u64, 0..1_000_000u64), 1..8).prop_map(|ranges| {
+            let mut src = 0;
+            let mut disjoint = vec![];
+            for (gap, len, dst) in ranges {
+                src += gap;
+                disjoint.push(Range { dst, src, len });
+                src += len;
+            }
+            disjoint
+        })
+    }
+
+    #[test]
+    fn prints_the_example() {
+        let almanac = parse::parse_all(parse, EXAMPLE).unwrap();
+        assert_eq!(almanac.to_string(), EXAMPLE.trim_end());
+    }
+
+    proptest! {
+        #[test]
+        fn almanac_round_trip(
+            seeds in prop::collection::vec(any::<u64>(), 1..10),
+            maps in prop::collection::vec(
+                prop::collection::vec(
+                    (any::<u64>(), any::<u64>(), any::<u64>())
+                        .prop_map(|(dst, src, len)| Range { dst, src, len }),
+                    1..5,
+                ),
+                MAPS.len(),
+            ),
+        ) {
+            let almanac = Almanac { seeds, maps };
+            prop_assert_eq!(parse::parse_all(parse, &almanac.to_string()).unwrap(), almanac);
+        }
+
+        #[test]
+        fn range_map_is_identity_outside_ranges(
+            ranges in disjoint_ranges(),
+            n in 0..10_000u64,
+        ) {
+            let map = RangeMap::new(&ranges).unwrap();
+            match ranges.iter().find(|range| (range.src..range.src + range.len).contains(&n)) {
+                Some(range) => prop_assert_eq!(map.get(n), range.dst + n - range.src),
+                None => prop_assert_eq!(map.get(n), n),
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_ranges() {
+        let range = |dst, src, len| Range { dst, src, len };
+        assert_eq!(
+            RangeMap::new(&[range(50, 98, 2), range(52, 50, 49)]).err(),
+            Some(AlmanacError::Overlap(range(52, 50, 49), range(50, 98, 2)))
+        );
+        assert_eq!(
+            RangeMap::new(&[range(u64::MAX, 0, 2)]).err(),
+            Some(AlmanacError::RangeOverflow(range(u64::MAX, 0, 2)))
+        );
+        assert_eq!(RangeMap::new(&[]).unwrap().get(7), 7);
+        assert_eq!(RangeMap::new(&[range(50, 98, 2)]).unwrap().get(99), 51);
+
+        let odd = EXAMPLE.replacen("seeds: 79 14", "seeds: 79", 1);
+        assert_eq!(part2(odd), Err(AlmanacError::OddSeeds));
+        let huge = EXAMPLE.replacen("seeds: 79", &format!("seeds: {}", u64::MAX), 1);
+        assert_eq!(
+            part2(huge),
+            Err(AlmanacError::SeedOverflow {
+                start: u64::MAX,
+                len: 14
+            })
+        );
+        let empty = EXAMPLE.replacen("seeds: 79 14 55 13", "seeds: 79 0 55 0", 1);
+        assert_eq!(part2(empty), Err(AlmanacError::NoSeeds));
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt;
+
+use super::{Error, Solution};
+use crate::parse::{self, ParseError};
+use nom::{
+    bytes::complete::tag, character::complete::digit1, combinator::map_res,
+    sequence::separated_pair, IResult,
+};
+
+#[derive(Debug, PartialEq)]
+pub enum RaceError {
+    Parse(ParseError),
+    /// The product of the ways to win does not fit in a `u64`.
+    Overflow,
+}
+
+impl fmt::Display for RaceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RaceError::Parse(err) => write!(f, "{}", err),
+            RaceError::Overflow => write!(f, "the number of ways to win overflows"),
+        }
+    }
+}
+
+impl std::error::Error for RaceError {}
+
+impl From<ParseError> for RaceError {
+    fn from(err: ParseError) -> Self {
+        RaceError::Parse(err)
+    }
+}
+
+/// Digits separated by spaces read as a single number, which fails to parse
+/// if it does not fit in a `u64`.
+fn kerned(input: &str) -> IResult<&str, u64> {
+    map_res(parse::list(digit1), |digits: Vec<&str>| {
+        digits.concat().parse::<u64>()
+    })(input)
+}
+
+pub fn parse_input(input: &str) -> IResult<&str, (Vec<u64>, Vec<u64>)> {
+    let (remaining, ((_, times), (_, distances))) = separated_pair(
+        parse::labelled(tag("Time"), parse::u64s),
+        parse::line_end,
+        parse::labelled(tag("Distance"), parse::u64s),
+    )(input)?;
+
+    Ok((remaining, (times, distances)))
+}
+
+pub fn parse_input2(input: &str) -> IResult<&str, (u64, u64)> {
+    let (remaining, ((_, times), (_, distances))) = separated_pair(
+        parse::labelled(tag("Time"), kerned),
+        parse::line_end,
+        parse::labelled(tag("Distance"), kerned),
+    )(input)?;
+
+    Ok((remaining, (times, distances)))
+}
+
+pub fn wins(race_duration: u64, record_dist: u64) -> f64 {
+    let r = race_duration as f64;
+    let d = record_dist as f64;
+    let sqrt = (r * r - 4.0 * d).sqrt();
+    let h1 = (-r + sqrt) / -2.0;
+    let h2 = (-r - sqrt) / -2.0;
+
+    if sqrt.fract() == 0.0 {
+        h2.floor() - h1.ceil() + 1.0 - 2.0
+    } else {
+        h2.floor() - h1.ceil() + 1.0
+    }
+}
+
+pub fn part1(input: String) -> Result<u64, RaceError> {
+    let (times, distances) = parse::parse_all(parse_input, &input)?;
+    let options: Vec<u64> = times
+        .iter()
+        .zip(distances.iter())
+        .map(|(race_time, record_dist)| wins(*race_time, *record_dist) as u64)
+        .collect();
+    let mut res: u64 = 1;
+    for n in options.iter() {
+        res = res.checked_mul(*n).ok_or(RaceError::Overflow)?;
+    }
+    Ok(res)
+}
+
+pub fn part2(input: String) -> Result<u64, RaceError> {
+    let (time, record_dist) = parse::parse_all(parse_input2, &input)?;
+    Ok(wins(time, record_dist) as u64)
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    fn part1(&self, input: &str) -> Result<String, Error> {
+        Ok(part1(input.to_string())?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, Error> {
+        Ok(part2(input.to_string())?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../data/day6/example");
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE.to_string()), Ok(288));
+        assert_eq!(part2(EXAMPLE.to_string()), Ok(71503));
+    }
+
+    #[test]
+    fn crlf_and_unterminated_input() {
+        for input in [
+            EXAMPLE.replace('\n', "\r\n"),
+            EXAMPLE.replace('\n', " \n"),
+            EXAMPLE.trim_end().to_string(),
+        ] {
+            assert_eq!(part1(input.clone()), Ok(288));
+            assert_eq!(part2(input), Ok(71503));
+        }
+    }
+
+    #[test]
+    fn huge_races() {
+        let input = format!("Time: {} 1\nDistance: 1 1\n", u64::MAX);
+        assert_eq!(
+            part2(input),
+            Err(RaceError::Parse(ParseError {
+                line: 1,
+                column: 7,
+                kind: nom::error::ErrorKind::MapRes,
+            }))
+        );
+        let input = "Time: 4294967295 4294967295 4294967295\nDistance: 1 1 1\n";
+        assert_eq!(part1(input.to_string()), Err(RaceError::Overflow));
+    }
+}
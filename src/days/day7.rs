@@ -0,0 +1,332 @@
+use std::fmt;
+
+use super::{no_such_part, Error, Lines, Solution, Unsolved};
+use crate::parse::{self, ParseError};
+use counter::Counter;
+use nom::{
+    character::complete::{one_of, space1},
+    multi::count,
+    sequence::separated_pair,
+    IResult,
+};
+
+#[derive(Debug, PartialEq)]
+pub enum HandError {
+    Parse(ParseError),
+    /// The total winnings do not fit in a `u64`.
+    Overflow,
+}
+
+impl fmt::Display for HandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HandError::Parse(err) => write!(f, "{}", err),
+            HandError::Overflow => write!(f, "the total winnings overflow"),
+        }
+    }
+}
+
+impl std::error::Error for HandError {}
+
+impl From<ParseError> for HandError {
+    fn from(err: ParseError) -> Self {
+        HandError::Parse(err)
+    }
+}
+
+#[derive(PartialEq, PartialOrd)]
+pub enum HandType {
+    FiveOfAKind,
+    FourOfAKind,
+    FullHouse,
+    ThreeOfAKind,
+    TwoPair,
+    OnePair,
+    HighCard,
+}
+
+#[derive(Eq, Debug)]
+pub struct Hand {
+    pub counts: Counter<char, usize>,
+    pub hand: Vec<char>,
+}
+
+impl Hand {
+    pub fn hand_type(&self) -> Result<HandType, String> {
+        let mut counts: Vec<(char, usize)> = self
+            .counts
+            .iter()
+            .map(|(card, count)| (*card, *count))
+            .collect();
+
+        let sorted_cards = vec![
+            'J', '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'Q', 'K', 'A',
+        ];
+        counts.sort_by(|a, b| {
+            if a.1 != b.1 {
+                return b.1.cmp(&a.1);
+            }
+            let pos1 = sorted_cards.iter().position(|c| *c == a.0).unwrap();
+            let pos2 = sorted_cards.iter().position(|c| *c == b.0).unwrap();
+            pos2.cmp(&pos1)
+        });
+
+        if counts.len() != 1 {
+            let index_j = counts.iter().position(|(hand, _)| *hand == 'J');
+            if let Some(index_j) = index_j {
+                for i in 0..counts.len() {
+                    if counts[i].0 != 'J' {
+                        counts[i].1 += counts[index_j].1;
+                        break;
+                    }
+                }
+                counts.remove(index_j);
+            }
+        }
+
+        let counts: Vec<usize> = counts.iter().map(|(_, count)| *count).collect();
+
+        match counts[..] {
+            [5] => Ok(HandType::FiveOfAKind),
+            [4, 1] => Ok(HandType::FourOfAKind),
+            [3, 2] => Ok(HandType::FullHouse),
+            [3, 1, 1] => Ok(HandType::ThreeOfAKind),
+            [2, 2, 1] => Ok(HandType::TwoPair),
+            [2, 1, 1, 1] => Ok(HandType::OnePair),
+            [1, 1, 1, 1, 1] => Ok(HandType::HighCard),
+            _ => Err(format!("{:?}", self.hand)),
+        }
+    }
+}
+
+impl PartialEq for Hand {
+    fn eq(&self, other: &Self) -> bool {
+        // Hands with the same cards in another order have the same counts
+        // but different ranks.
+        self.hand == other.hand
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let type1 = self.hand_type().unwrap();
+        let type2 = other.hand_type().unwrap();
+
+        if type1 < type2 {
+            return std::cmp::Ordering::Greater;
+        } else if type1 > type2 {
+            return std::cmp::Ordering::Less;
+        } else {
+            for (card1, card2) in self.hand.iter().zip(other.hand.iter()) {
+                if card1 == card2 {
+                    continue;
+                }
+                let sorted_cards = vec![
+                    'J', '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'Q', 'K', 'A',
+                ];
+                let pos1 = sorted_cards.iter().position(|c| c == card1);
+                let pos2 = sorted_cards.iter().position(|c| c == card2);
+                if pos1.unwrap() > pos2.unwrap() {
+                    return std::cmp::Ordering::Greater;
+                } else {
+                    return std::cmp::Ordering::Less;
+                }
+            }
+        }
+        std::cmp::Ordering::Equal
+    }
+}
+
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.hand.iter().collect::<String>())
+    }
+}
+
+/// Five cards and a bid. Any other card label is a parse error, so that
+/// every parsed hand has a type.
+pub fn parse_hand(input: &str) -> IResult<&str, (Hand, u32)> {
+    let (remaining, (hand, bid)) = separated_pair(
+        count(one_of("23456789TJQKA"), 5),
+        space1,
+        nom::character::complete::u32,
+    )(input)?;
+
+    Ok((
+        remaining,
+        (
+            Hand {
+                counts: hand.iter().copied().collect::<Counter<_>>(),
+                hand,
+            },
+            bid,
+        ),
+    ))
+}
+
+pub fn process(input: String) -> Result<u64, HandError> {
+    winnings(parse::parse_all(parse::lines(parse_hand), &input)?)
+}
+
+/// Hands and bids parsed one line at a time.
+pub fn stream_hands(lines: Lines) -> Result<Vec<(Hand, u32)>, Error> {
+    let mut hands = vec![];
+    for (index, line) in lines.enumerate() {
+        let hand = parse::parse_all(parse_hand, &line?).map_err(|err| ParseError {
+            line: index + 1,
+            ..err
+        })?;
+        hands.push(hand);
+    }
+    Ok(hands)
+}
+
+/// Sum of every bid multiplied by the rank of its hand. A `u64` holds any
+/// bid times any rank, but not necessarily the sum of them all.
+pub fn winnings(mut hands: Vec<(Hand, u32)>) -> Result<u64, HandError> {
+    hands.sort_by(|a, b| a.0.cmp(&b.0));
+    hands
+        .iter()
+        .enumerate()
+        .map(|(rank, (_, bid))| (rank as u64 + 1) * *bid as u64)
+        .try_fold(0u64, |total, winning| total.checked_add(winning))
+        .ok_or(HandError::Overflow)
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    fn part1(&self, _input: &str) -> Result<String, Error> {
+        Err(Unsolved.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, Error> {
+        Ok(process(input.to_string())?.to_string())
+    }
+
+    fn solve_lines(&self, part: u8, lines: Lines) -> Result<String, Error> {
+        match part {
+            1 => Err(Unsolved.into()),
+            2 => Ok(winnings(stream_hands(lines)?)?.to_string()),
+            _ => Err(no_such_part(part)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::cmp::Ordering;
+
+    const EXAMPLE: &str = include_str!("../data/day7/example");
+
+    #[test]
+    fn example() {
+        assert_eq!(process(EXAMPLE.to_string()), Ok(5905));
+    }
+
+    #[test]
+    fn winnings_overflow() {
+        let input = format!("AAAAA {}\n", u32::MAX).repeat(100_000);
+        assert_eq!(process(input), Err(HandError::Overflow));
+    }
+
+    #[test]
+    fn crlf_and_unterminated_input() {
+        for input in [
+            EXAMPLE.replace('\n', "\r\n"),
+            EXAMPLE.replace('\n', " \n"),
+            EXAMPLE.trim_end().to_string(),
+        ] {
+            assert_eq!(process(input), Ok(5905));
+        }
+    }
+
+    #[test]
+    fn streamed_lines() {
+        let mut lines = crate::utils::Lines::new(EXAMPLE.as_bytes());
+        assert_eq!(Day7.solve_lines(2, &mut lines).unwrap(), "5905");
+        let mut lines = crate::utils::Lines::new("32T3K 765\nT55J5".as_bytes());
+        assert_eq!(
+            Day7.solve_lines(2, &mut lines).unwrap_err().to_string(),
+            "parse error at line 2, column 6: expected Space"
+        );
+    }
+
+    #[test]
+    fn prints_the_example() {
+        let hands = parse::parse_all(parse::lines(parse_hand), EXAMPLE).unwrap();
+        let lines: Vec<String> = hands
+            .iter()
+            .map(|(hand, bid)| format!("{} {}", hand, bid))
+            .collect();
+        assert_eq!(lines, EXAMPLE.lines().collect::<Vec<_>>());
+    }
+
+    fn to_hand(cards: &[char]) -> Hand {
+        Hand {
+            counts: cards.iter().copied().collect(),
+            hand: cards.to_vec(),
+        }
+    }
+
+    fn hand() -> impl Strategy<Value = Hand> {
+        "[2-9TJQKA]{5}".prop_map(|cards| to_hand(&cards.chars().collect::<Vec<_>>()))
+    }
+
+    /// A hand and the same cards in another order.
+    fn reordered() -> impl Strategy<Value = (Hand, Hand)> {
+        "[2-9TJQKA]{5}"
+            .prop_flat_map(|cards| {
+                let cards: Vec<char> = cards.chars().collect();
+                (Just(cards.clone()), Just(cards).prop_shuffle())
+            })
+            .prop_map(|(a, b)| (to_hand(&a), to_hand(&b)))
+    }
+
+    proptest! {
+        #[test]
+        fn hands_round_trip(
+            hands in prop::collection::vec((hand(), any::<u32>()), 1..10)
+        ) {
+            let text: Vec<String> = hands
+                .iter()
+                .map(|(hand, bid)| format!("{} {}", hand, bid))
+                .collect();
+            let parsed = parse::parse_all(parse::lines(parse_hand), &text.join("\n")).unwrap();
+            prop_assert_eq!(parsed, hands);
+        }
+
+        #[test]
+        fn hands_are_totally_ordered(a in hand(), b in hand(), c in hand()) {
+            prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+            prop_assert_eq!(a.cmp(&b) == Ordering::Equal, a == b);
+            if a <= b && b <= c {
+                prop_assert!(a <= c);
+            }
+        }
+
+        #[test]
+        fn reordered_hands_are_only_equal_if_identical((a, b) in reordered()) {
+            prop_assert_eq!(a.cmp(&b) == Ordering::Equal, a == b);
+            prop_assert_eq!(a == b, a.hand == b.hand);
+        }
+
+        #[test]
+        fn sorted_hands_are_in_order(mut hands in prop::collection::vec(hand(), 1..30)) {
+            hands.sort();
+            for (index, a) in hands.iter().enumerate() {
+                for b in &hands[index..] {
+                    prop_assert_ne!(a.cmp(b), Ordering::Greater);
+                }
+            }
+        }
+    }
+}
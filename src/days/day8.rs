@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::{Error, Solution, Unsolved};
+use crate::parse::{self, ParseError};
+use nom::{
+    bytes::complete::{is_a, tag},
+    character::complete::alphanumeric1,
+    sequence::{delimited, separated_pair},
+    IResult,
+};
+
+#[derive(Debug, PartialEq)]
+pub enum NetworkError {
+    Parse(ParseError),
+    UnknownNode(String),
+    DuplicateNode(String),
+    /// The walk from this node loops without reaching a node ending in Z.
+    NoExit(String),
+    NoStart,
+    Overflow,
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NetworkError::Parse(err) => write!(f, "{}", err),
+            NetworkError::UnknownNode(node) => write!(f, "node {} is not in the network", node),
+            NetworkError::DuplicateNode(node) => write!(f, "node {} is defined twice", node),
+            NetworkError::NoExit(node) => {
+                write!(f, "the walk from {} never reaches a node ending in Z", node)
+            }
+            NetworkError::NoStart => write!(f, "no node ends in A"),
+            NetworkError::Overflow => write!(f, "the number of steps overflows"),
+        }
+    }
+}
+
+impl std::error::Error for NetworkError {}
+
+impl From<ParseError> for NetworkError {
+    fn from(err: ParseError) -> Self {
+        NetworkError::Parse(err)
+    }
+}
+
+pub type Network<'a> = HashMap<&'a str, (&'a str, &'a str)>;
+
+/// A node with the nodes its left and right instructions lead to.
+pub type Node<'a> = (&'a str, (&'a str, &'a str));
+
+/// The left/right instructions and the nodes of the network they walk, in
+/// the order of the input.
+#[derive(Debug, PartialEq)]
+pub struct Documents<'a> {
+    pub lr_steps: Vec<char>,
+    pub nodes: Vec<Node<'a>>,
+}
+
+impl<'a> Documents<'a> {
+    /// The nodes by name, or an error if a node is defined twice.
+    pub fn network(&self) -> Result<Network<'a>, NetworkError> {
+        let mut network = Network::new();
+        for (node, targets) in &self.nodes {
+            if network.insert(*node, *targets).is_some() {
+                return Err(NetworkError::DuplicateNode(node.to_string()));
+            }
+        }
+        Ok(network)
+    }
+}
+
+impl fmt::Display for Documents<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lr_steps: String = self.lr_steps.iter().collect();
+        writeln!(f, "{}", lr_steps)?;
+        for (node, (left, right)) in &self.nodes {
+            write!(f, "\n{} = ({}, {})", node, left, right)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn parse(input: &str) -> IResult<&str, Documents<'_>> {
+    let (remaining, (lr_steps, nodes)) = separated_pair(
+        is_a("LR"),
+        parse::blank_line,
+        parse::key_values(
+            alphanumeric1,
+            delimited(
+                tag("("),
+                separated_pair(alphanumeric1, tag(", "), alphanumeric1),
+                tag(")"),
+            ),
+        ),
+    )(input)?;
+
+    Ok((
+        remaining,
+        Documents {
+            lr_steps: lr_steps.chars().collect(),
+            nodes,
+        },
+    ))
+}
+
+/// A walk through the network from `start`, one step at a time.
+pub struct Walk<'a> {
+    pub start: &'a str,
+    pub node: &'a str,
+    pub steps: u64,
+}
+
+impl<'a> Walk<'a> {
+    pub fn new(start: &'a str) -> Self {
+        Self {
+            start,
+            node: start,
+            steps: 0,
+        }
+    }
+
+    pub fn at_exit(&self) -> bool {
+        self.node.ends_with('Z')
+    }
+
+    /// Index into the instructions of the one the next step follows.
+    pub fn instruction(&self, lr_steps: &[char]) -> usize {
+        (self.steps % lr_steps.len().max(1) as u64) as usize
+    }
+
+    /// Follows the next instruction and returns the node it leads to.
+    pub fn step(
+        &mut self,
+        network: &Network<'a>,
+        lr_steps: &[char],
+    ) -> Result<&'a str, NetworkError> {
+        let instruction = lr_steps
+            .get(self.instruction(lr_steps))
+            .ok_or_else(|| NetworkError::NoExit(self.start.to_string()))?;
+        let (left, right) = network
+            .get(self.node)
+            .ok_or_else(|| NetworkError::UnknownNode(self.node.to_string()))?;
+        self.node = if *instruction == 'R' { right } else { left };
+        self.steps += 1;
+        Ok(self.node)
+    }
+}
+
+/// Steps from `node` to the first node ending in Z.
+pub fn play<'a>(node: &'a str, map: &Network<'a>, lr_steps: &[char]) -> Result<u64, NetworkError> {
+    let mut walk = Walk::new(node);
+    while !walk.at_exit() {
+        if walk.steps > step_limit(map, lr_steps) {
+            return Err(NetworkError::NoExit(node.to_string()));
+        }
+        walk.step(map, lr_steps)?;
+    }
+    Ok(walk.steps)
+}
+
+/// Steps after which a walk that has not reached a node ending in Z never
+/// will, having visited every node at every instruction.
+pub fn step_limit(map: &Network, lr_steps: &[char]) -> u64 {
+    (map.len() * lr_steps.len()) as u64
+}
+
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        return a;
+    }
+    gcd(b, a % b)
+}
+
+/// Least common multiple of `nums`, or `None` if there are none or it does
+/// not fit in a `u64`.
+pub fn lcm(nums: &[u64]) -> Option<u64> {
+    match nums {
+        [] => None,
+        [a] => Some(*a),
+        [a, rest @ ..] => {
+            let b = lcm(rest)?;
+            (a / gcd(*a, b)).checked_mul(b)
+        }
+    }
+}
+
+pub fn process(input: String) -> Result<u64, NetworkError> {
+    let documents = parse::parse_all(parse, &input)?;
+    let map = documents.network()?;
+
+    let mut current_nodes: Vec<&str> = vec![];
+    for (node, _) in &documents.nodes {
+        if node.ends_with('A') {
+            current_nodes.push(node);
+        }
+    }
+    if current_nodes.is_empty() {
+        return Err(NetworkError::NoStart);
+    }
+
+    let nums = current_nodes
+        .iter()
+        .map(|node| play(node, &map, &documents.lr_steps))
+        .collect::<Result<Vec<u64>, _>>()?;
+
+    lcm(&nums).ok_or(NetworkError::Overflow)
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    fn part1(&self, _input: &str) -> Result<String, Error> {
+        Err(Unsolved.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, Error> {
+        Ok(process(input.to_string())?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../data/day8/example");
+
+    #[test]
+    fn example() {
+        assert_eq!(process(EXAMPLE.to_string()), Ok(6));
+    }
+
+    #[test]
+    fn crlf_and_unterminated_input() {
+        for input in [
+            EXAMPLE.replace('\n', "\r\n"),
+            EXAMPLE.replace('\n', " \n"),
+            EXAMPLE.trim_end().to_string(),
+        ] {
+            assert_eq!(process(input), Ok(6));
+        }
+    }
+
+    #[test]
+    fn prints_the_example() {
+        let documents = parse::parse_all(parse, EXAMPLE).unwrap();
+        assert_eq!(documents.to_string(), EXAMPLE.trim_end());
+    }
+
+    proptest! {
+        #[test]
+        fn network_round_trip(
+            lr_steps in "[LR]{1,20}",
+            nodes in prop::collection::vec(
+                ("[0-9A-Z]{3}", ("[0-9A-Z]{3}", "[0-9A-Z]{3}")),
+                1..20,
+            ),
+        ) {
+            let documents = Documents {
+                lr_steps: lr_steps.chars().collect(),
+                nodes: nodes
+                    .iter()
+                    .map(|(node, (left, right))| (node.as_str(), (left.as_str(), right.as_str())))
+                    .collect(),
+            };
+            let text = documents.to_string();
+            prop_assert_eq!(parse::parse_all(parse, &text).unwrap(), documents);
+        }
+    }
+
+    #[test]
+    fn broken_networks() {
+        let errors = [
+            (
+                "LR\n\nAAA = (BBB, ZZZ)\n",
+                NetworkError::UnknownNode("BBB".into()),
+            ),
+            (
+                "L\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n",
+                NetworkError::NoExit("AAA".into()),
+            ),
+            ("L\n\nBBB = (ZZZ, ZZZ)\n", NetworkError::NoStart),
+            (
+                "L\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)\nAAA = (AAA, AAA)\n",
+                NetworkError::DuplicateNode("AAA".into()),
+            ),
+        ];
+        for (input, error) in errors {
+            assert_eq!(process(input.to_string()), Err(error));
+        }
+        assert!(matches!(
+            process("LX\n\nAAA = (ZZZ, ZZZ)\n".to_string()),
+            Err(NetworkError::Parse(_))
+        ));
+    }
+}
@@ -0,0 +1,297 @@
+use std::fmt;
+use std::io;
+
+use super::{no_such_part, Error, Lines, Solution};
+
+#[derive(Debug, PartialEq)]
+pub enum HistoryError {
+    Number(String),
+    /// A difference or prediction does not fit in an `i64`.
+    Overflow,
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HistoryError::Number(number) => write!(f, "invalid number '{}'", number),
+            HistoryError::Overflow => write!(f, "the prediction overflows"),
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {}
+
+pub fn differences(values: &[i64]) -> Result<Vec<i64>, HistoryError> {
+    values
+        .windows(2)
+        .map(|pair| pair[1].checked_sub(pair[0]).ok_or(HistoryError::Overflow))
+        .collect()
+}
+
+/// The differences of a history, level after level, down to a level of
+/// zeros.
+#[derive(Debug, PartialEq)]
+pub struct Pyramid {
+    /// The history first and the zeros last.
+    pub levels: Vec<Vec<i64>>,
+}
+
+impl Pyramid {
+    /// The whole pyramid of `values`.
+    pub fn new(values: &[i64]) -> Result<Self, HistoryError> {
+        let mut pyramid = Self::start(values);
+        while !pyramid.is_complete() {
+            pyramid.step()?;
+        }
+        Ok(pyramid)
+    }
+
+    /// A pyramid holding only `values`, to be built one level at a time.
+    pub fn start(values: &[i64]) -> Self {
+        Self {
+            levels: vec![values.to_vec()],
+        }
+    }
+
+    /// Whether the last level is all zeros.
+    pub fn is_complete(&self) -> bool {
+        self.levels
+            .last()
+            .is_none_or(|level| level.iter().all(|value| *value == 0))
+    }
+
+    /// Adds the differences of the last level, unless it is all zeros.
+    pub fn step(&mut self) -> Result<(), HistoryError> {
+        if let Some(level) = self.levels.last().filter(|_| !self.is_complete()) {
+            let next = differences(level)?;
+            self.levels.push(next);
+        }
+        Ok(())
+    }
+
+    /// The value after the end of each level.
+    pub fn next_values(&self) -> Result<Vec<i64>, HistoryError> {
+        let mut values = vec![0; self.levels.len()];
+        for (index, level) in self.levels.iter().enumerate().rev().skip(1) {
+            // Only the zeros can be empty, and they are skipped.
+            values[index] = level[level.len() - 1]
+                .checked_add(values[index + 1])
+                .ok_or(HistoryError::Overflow)?;
+        }
+        Ok(values)
+    }
+
+    /// The value before the start of each level.
+    pub fn previous_values(&self) -> Result<Vec<i64>, HistoryError> {
+        let mut values = vec![0; self.levels.len()];
+        for (index, level) in self.levels.iter().enumerate().rev().skip(1) {
+            values[index] = level[0]
+                .checked_sub(values[index + 1])
+                .ok_or(HistoryError::Overflow)?;
+        }
+        Ok(values)
+    }
+}
+
+/// The levels staggered under each other, with the extrapolated values in
+/// brackets at both ends, or a `?` where they overflow.
+impl fmt::Display for Pyramid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let extrapolated = |values: Result<Vec<i64>, HistoryError>| -> Vec<String> {
+            match values {
+                Ok(values) => values.iter().map(|value| format!("[{}]", value)).collect(),
+                Err(_) => vec!["[?]".to_string(); self.levels.len()],
+            }
+        };
+        let previous = extrapolated(self.previous_values());
+        let next = extrapolated(self.next_values());
+        let width = self
+            .levels
+            .iter()
+            .flatten()
+            .map(|value| value.to_string())
+            .chain(previous.iter().chain(&next).cloned())
+            .map(|cell| cell.len())
+            .max()
+            .unwrap_or(0)
+            + 1;
+        for (depth, level) in self.levels.iter().enumerate() {
+            if depth > 0 {
+                writeln!(f)?;
+            }
+            let mut line = " ".repeat(depth * width / 2);
+            let cells = [&previous[depth]]
+                .into_iter()
+                .cloned()
+                .chain(level.iter().map(|value| value.to_string()))
+                .chain([next[depth].clone()]);
+            for cell in cells {
+                line.push_str(&format!("{:>width$}", cell, width = width));
+            }
+            write!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+pub fn predict(values: &[i64]) -> Result<i64, HistoryError> {
+    Ok(Pyramid::new(values)?.next_values()?[0])
+}
+
+pub fn predict_past(values: &[i64]) -> Result<i64, HistoryError> {
+    Ok(Pyramid::new(values)?.previous_values()?[0])
+}
+
+pub fn history(line: &str) -> Result<Vec<i64>, HistoryError> {
+    line.split_whitespace()
+        .map(|number| {
+            number
+                .parse::<i64>()
+                .map_err(|_| HistoryError::Number(number.to_string()))
+        })
+        .collect()
+}
+
+/// Sum of the extrapolated values of `lines`, which may come straight from a
+/// file.
+fn sum<S: AsRef<str>>(
+    lines: impl Iterator<Item = io::Result<S>>,
+    extrapolate: fn(&[i64]) -> Result<i64, HistoryError>,
+) -> Result<i64, Error> {
+    let mut sum: i64 = 0;
+    for line in lines {
+        let value = extrapolate(&history(line?.as_ref())?)?;
+        sum = sum.checked_add(value).ok_or(HistoryError::Overflow)?;
+    }
+    Ok(sum)
+}
+
+pub fn part1(input: &str) -> Result<i64, Error> {
+    sum(input.lines().map(Ok), predict)
+}
+
+pub fn part2(input: &str) -> Result<i64, Error> {
+    sum(input.lines().map(Ok), predict_past)
+}
+
+/// The pyramid of every history of `input`, separated by blank lines.
+pub fn explain(input: &str) -> Result<String, HistoryError> {
+    let mut pyramids = vec![];
+    for line in input.lines() {
+        pyramids.push(format!("{}\n", Pyramid::new(&history(line)?)?));
+    }
+    Ok(pyramids.join("\n"))
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    fn part1(&self, input: &str) -> Result<String, Error> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, Error> {
+        Ok(part2(input)?.to_string())
+    }
+
+    fn solve_lines(&self, part: u8, lines: Lines) -> Result<String, Error> {
+        let extrapolate = match part {
+            1 => predict,
+            2 => predict_past,
+            _ => return Err(no_such_part(part)),
+        };
+        Ok(sum(lines, extrapolate)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../data/day9/example");
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 114);
+        assert_eq!(part2(EXAMPLE).unwrap(), 2);
+    }
+
+    #[test]
+    fn crlf_and_unterminated_input() {
+        for input in [
+            EXAMPLE.replace('\n', "\r\n"),
+            EXAMPLE.replace('\n', " \n"),
+            EXAMPLE.trim_end().to_string(),
+        ] {
+            assert_eq!(part1(&input).unwrap(), 114);
+            assert_eq!(part2(&input).unwrap(), 2);
+        }
+    }
+
+    #[test]
+    fn streamed_lines() {
+        for (part, expected) in [(1, "114"), (2, "2")] {
+            let mut lines = crate::utils::Lines::new(EXAMPLE.as_bytes());
+            assert_eq!(Day9.solve_lines(part, &mut lines).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn malformed_histories() {
+        assert_eq!(
+            part1("0 3 6 9 12 15\n1 3 \u{3}\n").unwrap_err().to_string(),
+            "invalid number '\u{3}'"
+        );
+        let huge = format!("{} {}\n", i64::MIN, i64::MAX);
+        assert_eq!(
+            part2(&huge).unwrap_err().to_string(),
+            "the prediction overflows"
+        );
+        let sum = format!("{}\n1\n", i64::MAX);
+        assert!(part1(&sum).is_err());
+    }
+
+    #[test]
+    fn explained_pyramid() {
+        let pyramid = Pyramid::new(&[10, 13, 16, 21, 30, 45]).unwrap();
+        assert_eq!(
+            pyramid.levels,
+            [
+                vec![10, 13, 16, 21, 30, 45],
+                vec![3, 3, 5, 9, 15],
+                vec![0, 2, 4, 6],
+                vec![2, 2, 2],
+                vec![0, 0],
+            ]
+        );
+        assert_eq!(pyramid.next_values(), Ok(vec![68, 23, 8, 2, 0]));
+        assert_eq!(pyramid.previous_values(), Ok(vec![5, 5, -2, 2, 0]));
+        assert_eq!(
+            pyramid.to_string(),
+            "  [5]   10   13   16   21   30   45 [68]\n\
+             \x20   [5]    3    3    5    9   15 [23]\n\
+             \x20     [-2]    0    2    4    6  [8]\n\
+             \x20        [2]    2    2    2  [2]\n\
+             \x20           [0]    0    0  [0]"
+        );
+
+        let mut pyramid = Pyramid::start(&[1, 4, 9]);
+        pyramid.step().unwrap();
+        assert_eq!(pyramid.levels, [vec![1, 4, 9], vec![3, 5]]);
+        assert!(!pyramid.is_complete());
+        pyramid.step().unwrap();
+        pyramid.step().unwrap();
+        assert!(pyramid.is_complete());
+        pyramid.step().unwrap();
+        assert_eq!(pyramid, Pyramid::new(&[1, 4, 9]).unwrap());
+
+        let pyramid = Pyramid::new(&[0, i64::MAX]).unwrap();
+        assert!(pyramid.to_string().contains("[?]"));
+        assert_eq!(Pyramid::new(&[]).unwrap().to_string(), " [0] [0]");
+
+        let explained = explain(EXAMPLE).unwrap();
+        assert!(explained.starts_with(" [-3]    0    3"));
+        assert!(explained.contains("  [0]\n\n  [0]    1    3"));
+        assert!(explained.ends_with("  [0]\n"));
+    }
+}
@@ -0,0 +1,88 @@
+//! Puzzle solutions, one module per day, all reachable through [`get`] so
+//! that tools can solve any day without knowing its details.
+
+use std::fmt;
+use std::io;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// Lines of an input as produced by [`crate::utils::read_lines`].
+pub type Lines<'a> = &'a mut dyn Iterator<Item = io::Result<String>>;
+
+/// A day's puzzle solved with its default settings. Answers are returned as
+/// the text that would be typed into the website.
+pub trait Solution: Sync {
+    fn part1(&self, input: &str) -> Result<String, Error>;
+    fn part2(&self, input: &str) -> Result<String, Error>;
+
+    fn solve(&self, part: u8, input: &str) -> Result<String, Error> {
+        match part {
+            1 => self.part1(input),
+            2 => self.part2(input),
+            _ => Err(no_such_part(part)),
+        }
+    }
+
+    /// Solves `part` reading the input one line at a time. Days whose input
+    /// is a list of independent lines override this so that huge inputs
+    /// never have to be held in memory; the others collect the lines first.
+    fn solve_lines(&self, part: u8, lines: Lines) -> Result<String, Error> {
+        let mut input = String::new();
+        for line in lines {
+            input.push_str(&line?);
+            input.push('\n');
+        }
+        self.solve(part, &input)
+    }
+}
+
+fn no_such_part(part: u8) -> Error {
+    format!("there is no part {}", part).into()
+}
+
+/// Returned for a part that has not been solved in this repository.
+#[derive(Debug)]
+pub struct Unsolved;
+
+impl fmt::Display for Unsolved {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "this part has not been solved yet")
+    }
+}
+
+impl std::error::Error for Unsolved {}
+
+static SOLUTIONS: &[(u32, &dyn Solution)] = &[
+    (1, &day1::Day1),
+    (2, &day2::Day2),
+    (3, &day3::Day3),
+    (4, &day4::Day4),
+    (5, &day5::Day5),
+    (6, &day6::Day6),
+    (7, &day7::Day7),
+    (8, &day8::Day8),
+    (9, &day9::Day9),
+];
+
+/// Solution of `day`, if there is one.
+pub fn get(day: u32) -> Option<&'static dyn Solution> {
+    SOLUTIONS
+        .iter()
+        .find(|(solved, _)| *solved == day)
+        .map(|(_, solution)| *solution)
+}
+
+/// Every solved day, in order.
+pub fn days() -> impl Iterator<Item = u32> {
+    SOLUTIONS.iter().map(|(day, _)| *day)
+}
@@ -0,0 +1,195 @@
+//! Entry point of the fuzz targets in `fuzz/`, one per day. Run them with
+//! `cargo +nightly fuzz run day4`. Inputs that crashed a day are kept in
+//! `fuzz/regressions/dayN/` and replayed by the tests, which pin the answer
+//! or error each of them must keep giving.
+
+use crate::days::{self, day5};
+use crate::parse;
+
+/// Most seeds day 5 part 2 may try. It tries every seed of every range,
+/// which takes forever on the huge ranges fuzzers like to write.
+const MAX_SEEDS: u64 = 1 << 16;
+
+/// Seeds day 5 part 2 tries on `input`, or 0 if it rejects the input before
+/// trying any.
+fn seeds_to_try(input: &str) -> u64 {
+    let Ok(almanac) = parse::parse_all(day5::parse, input) else {
+        return 0;
+    };
+    let pairs = almanac.seeds.chunks_exact(2);
+    if !pairs.remainder().is_empty()
+        || pairs
+            .clone()
+            .any(|pair| pair[0].checked_add(pair[1]).is_none())
+    {
+        return 0;
+    }
+    pairs.map(|pair| pair[1]).fold(0, u64::saturating_add)
+}
+
+/// Solves both parts of `day` on `input`. Malformed inputs must give an
+/// error; a panic is a bug.
+pub fn solve(day: u32, input: &str) {
+    if let Some(solution) = days::get(day) {
+        for part in [1, 2] {
+            if (day, part) == (5, 2) && seeds_to_try(input) > MAX_SEEDS {
+                continue;
+            }
+            let _ = solution.solve(part, input);
+        }
+    }
+}
+
+/// The text a fuzz target sees for `data`: its longest valid UTF-8 prefix.
+pub fn text(data: &[u8]) -> &str {
+    match std::str::from_utf8(data) {
+        Ok(text) => text,
+        Err(err) => std::str::from_utf8(&data[..err.valid_up_to()]).unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day2::GameError;
+    use crate::days::day3::SchematicError;
+    use crate::days::day4::SimulationError;
+    use crate::days::day5::{AlmanacError, Range};
+    use crate::days::day6::RaceError;
+    use crate::days::day7::HandError;
+    use crate::days::day8::NetworkError;
+    use crate::days::day9::HistoryError;
+    use crate::parse::ParseError;
+    use nom::error::ErrorKind;
+    use std::fs;
+    use std::path::Path;
+
+    fn answer(day: u32, part: u8, input: &str) -> String {
+        days::get(day).unwrap().solve(part, input).unwrap()
+    }
+
+    /// Asserts that `part` of `day` rejects `input` with `expected`.
+    fn assert_fails<E>(day: u32, part: u8, input: &str, expected: E)
+    where
+        E: std::error::Error + PartialEq + 'static,
+    {
+        let err = days::get(day).unwrap().solve(part, input).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<E>(),
+            Some(&expected),
+            "day {} part {}: {}",
+            day,
+            part,
+            err
+        );
+    }
+
+    fn parse_error(line: usize, column: usize, kind: ErrorKind) -> ParseError {
+        ParseError { line, column, kind }
+    }
+
+    #[test]
+    fn regressions() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fuzz/regressions");
+        let mut replayed = 0;
+        for day in days::days() {
+            if let Ok(entries) = fs::read_dir(dir.join(format!("day{}", day))) {
+                for entry in entries {
+                    let path = entry.unwrap().path();
+                    let data = fs::read(&path).unwrap();
+                    let input = text(&data);
+                    solve(day, input);
+                    match (day, path.file_name().unwrap().to_str().unwrap()) {
+                        (1, "non-ascii") => {
+                            assert_eq!(answer(1, 1, input), "49");
+                            assert_eq!(answer(1, 2, input), "49");
+                        }
+                        (2, "power-overflow") => assert_fails(2, 2, input, GameError::Overflow),
+                        (3, "huge-number") => {
+                            let number = SchematicError::Number {
+                                row: 1,
+                                text: "99999999999".to_string(),
+                            };
+                            assert_fails(3, 1, input, number);
+                        }
+                        (3, "product-overflow") => {
+                            assert_fails(3, 2, input, SchematicError::Overflow)
+                        }
+                        (4, "too-many-copies") => {
+                            let copies = SimulationError::TooManyCopies { card: 91 };
+                            assert_fails(4, 2, input, copies);
+                        }
+                        (5, "overlapping-ranges") => {
+                            let overlap = AlmanacError::Overlap(
+                                Range {
+                                    dst: 52,
+                                    src: 50,
+                                    len: 48,
+                                },
+                                Range {
+                                    dst: 50,
+                                    src: 87,
+                                    len: 2,
+                                },
+                            );
+                            assert_fails(5, 1, input, overlap);
+                        }
+                        (5, "range-overflow") => {
+                            let range = Range {
+                                dst: u64::MAX,
+                                src: 98,
+                                len: 2,
+                            };
+                            assert_fails(5, 1, input, AlmanacError::RangeOverflow(range));
+                        }
+                        (6, "kerned-overflow") => {
+                            let error = parse_error(2, 12, ErrorKind::MapRes);
+                            assert_fails(6, 2, input, RaceError::Parse(error));
+                        }
+                        (6, "product-overflow") => assert_fails(6, 1, input, RaceError::Overflow),
+                        (7, "unknown-card") => {
+                            let error = parse_error(5, 1, ErrorKind::Eof);
+                            assert_fails(7, 2, input, HandError::Parse(error));
+                        }
+                        (8, "no-exit") => {
+                            assert_fails(8, 2, input, NetworkError::NoExit("AAA".into()))
+                        }
+                        (8, "no-start") => assert_fails(8, 2, input, NetworkError::NoStart),
+                        (8, "unknown-node") => {
+                            assert_fails(8, 2, input, NetworkError::UnknownNode("BBB".into()))
+                        }
+                        (9, "invalid-number") => {
+                            let number = HistoryError::Number("\u{3}".to_string());
+                            assert_fails(9, 1, input, number);
+                        }
+                        (9, "overflow") => assert_fails(9, 1, input, HistoryError::Overflow),
+                        (day, name) => panic!("no outcome is expected of day {} {}", day, name),
+                    }
+                    replayed += 1;
+                }
+            }
+        }
+        assert!(replayed > 0);
+    }
+
+    #[test]
+    fn day5_part2_is_bounded() {
+        let example = include_str!("data/day5/examples/part1.txt");
+        assert_eq!(seeds_to_try(example), 27);
+        let huge = example.replacen("seeds: 79 14", "seeds: 79 100000000000", 1);
+        assert!(seeds_to_try(&huge) > MAX_SEEDS);
+        solve(5, &huge);
+
+        // Rejected before any seed is tried, so still fuzzed.
+        let odd = example.replacen("seeds: 79 14", "seeds: 79", 1);
+        assert_eq!(seeds_to_try(&odd), 0);
+        let overflow = example.replacen("seeds: 79", &format!("seeds: {}", u64::MAX), 1);
+        assert_eq!(seeds_to_try(&overflow), 0);
+    }
+
+    #[test]
+    fn invalid_utf8_is_cut() {
+        assert_eq!(text(b"1abc\xff2"), "1abc");
+        assert_eq!(text(b"1abc2"), "1abc2");
+    }
+}
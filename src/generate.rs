@@ -0,0 +1,461 @@
+//! Random puzzle inputs for stress tests. Every generator is deterministic
+//! for a given seed and `size`, and produces inputs that the solutions can
+//! answer without overflowing.
+
+use std::collections::HashSet;
+use std::fmt::{self, Write};
+
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::days::day5::MAPS;
+
+type Generator = fn(&mut ChaCha8Rng, usize) -> String;
+
+/// Generators with the largest `size` they can produce. The smallest is 1.
+static GENERATORS: &[(u32, Generator, usize)] = &[
+    (1, calibration, usize::MAX),
+    (2, games, usize::MAX),
+    (3, schematic, usize::MAX),
+    (4, scratchcards, usize::MAX),
+    (5, almanac, usize::MAX),
+    (6, races, 4),
+    (7, hands, usize::MAX),
+    (8, network, MAX_STEPS),
+    (9, histories, usize::MAX),
+];
+
+#[derive(Debug, PartialEq)]
+pub enum GenerateError {
+    NoGenerator(u32),
+    /// Size 0, which no generator produces.
+    Empty(u32),
+    TooLarge {
+        day: u32,
+        size: usize,
+        max: usize,
+    },
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GenerateError::NoGenerator(day) => write!(f, "there is no generator for day {}", day),
+            GenerateError::Empty(day) => {
+                write!(
+                    f,
+                    "the generator for day {} needs a size of at least 1",
+                    day
+                )
+            }
+            GenerateError::TooLarge { day, size, max } => write!(
+                f,
+                "the generator for day {} goes up to size {}, not {}",
+                day, max, size
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GenerateError {}
+
+/// Input of `day` for `seed`. `size` is the number of lines, cards, hands...
+/// depending on the day, from 1 up to [`max_size`].
+pub fn generate(day: u32, seed: u64, size: usize) -> Result<String, GenerateError> {
+    let max = max_size(day).ok_or(GenerateError::NoGenerator(day))?;
+    if size == 0 {
+        return Err(GenerateError::Empty(day));
+    }
+    if size > max {
+        return Err(GenerateError::TooLarge { day, size, max });
+    }
+    let (_, generator, _) = GENERATORS
+        .iter()
+        .find(|(generated, _, _)| *generated == day)
+        .unwrap();
+    Ok(generator(&mut ChaCha8Rng::seed_from_u64(seed), size))
+}
+
+/// Largest `size` the generator of `day` can produce, or `None` if the day
+/// has no generator.
+pub fn max_size(day: u32) -> Option<usize> {
+    GENERATORS
+        .iter()
+        .find(|(generated, _, _)| *generated == day)
+        .map(|(_, _, max)| *max)
+}
+
+/// Every day with a generator, in order.
+pub fn days() -> impl Iterator<Item = u32> {
+    GENERATORS.iter().map(|(day, _, _)| *day)
+}
+
+const DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn letters(rng: &mut ChaCha8Rng, len: usize) -> String {
+    (0..len).map(|_| rng.gen_range('a'..='z')).collect()
+}
+
+/// Lines of letters, digit words and digits, each with at least one digit.
+fn calibration(rng: &mut ChaCha8Rng, size: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let mut pieces: Vec<String> = (0..rng.gen_range(1..8))
+            .map(|_| match rng.gen_range(0..3) {
+                0 => {
+                    let len = rng.gen_range(1..5);
+                    letters(rng, len)
+                }
+                1 => DIGIT_WORDS.choose(rng).unwrap().to_string(),
+                _ => rng.gen_range(1..=9).to_string(),
+            })
+            .collect();
+        let at = rng.gen_range(0..=pieces.len());
+        pieces.insert(at, rng.gen_range(1..=9).to_string());
+        input.push_str(&pieces.concat());
+        input.push('\n');
+    }
+    input
+}
+
+fn games(rng: &mut ChaCha8Rng, size: usize) -> String {
+    let mut input = String::new();
+    for id in 1..=size {
+        let rounds: Vec<String> = (0..rng.gen_range(1..=6))
+            .map(|_| {
+                let mut colors = ["red", "green", "blue"];
+                colors.shuffle(rng);
+                colors[..rng.gen_range(1..=3)]
+                    .iter()
+                    .map(|color| format!("{} {}", rng.gen_range(1..=20), color))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect();
+        writeln!(input, "Game {}: {}", id, rounds.join("; ")).unwrap();
+    }
+    input
+}
+
+const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '@', '=', '%', '&', '-'];
+
+/// Square grid of `size` cells a side, with numbers always followed by a
+/// dot or the edge so that neighbouring numbers never merge.
+fn schematic(rng: &mut ChaCha8Rng, size: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let mut row = String::new();
+        while row.len() < size {
+            let left = size - row.len();
+            match rng.gen_range(0..10) {
+                0 => row.push(*SYMBOLS.choose(rng).unwrap()),
+                1..=2 => {
+                    let digits = rng.gen_range(1..=3.min(left));
+                    let number =
+                        rng.gen_range(10u32.pow(digits as u32 - 1)..10u32.pow(digits as u32));
+                    write!(row, "{}", number).unwrap();
+                    if row.len() < size {
+                        row.push('.');
+                    }
+                }
+                _ => row.push('.'),
+            }
+        }
+        input.push_str(&row);
+        input.push('\n');
+    }
+    input
+}
+
+/// Distinct numbers below 100.
+fn distinct(rng: &mut ChaCha8Rng, count: usize, exclude: &HashSet<u32>) -> Vec<u32> {
+    let mut pool: Vec<u32> = (1..100).filter(|n| !exclude.contains(n)).collect();
+    pool.shuffle(rng);
+    pool.truncate(count);
+    pool
+}
+
+/// Cards that never win copies past the last card, with few enough matches
+/// that the number of copies stays far below `u64::MAX`.
+fn scratchcards(rng: &mut ChaCha8Rng, size: usize) -> String {
+    const LIMIT: u64 = 1 << 40;
+    let mut copies = vec![1u64; size];
+    let mut input = String::new();
+    for index in 0..size {
+        let mut matches = if rng.gen_bool(0.5) {
+            0
+        } else {
+            rng.gen_range(1..=10).min(size - index - 1)
+        };
+        while copies[index + 1..=index + matches]
+            .iter()
+            .any(|count| count + copies[index] > LIMIT)
+        {
+            matches -= 1;
+        }
+        for i in index + 1..=index + matches {
+            copies[i] += copies[index];
+        }
+
+        let winning = distinct(rng, 10, &HashSet::new());
+        let mut owned = distinct(rng, 25 - matches, &winning.iter().copied().collect());
+        owned.extend(winning.choose_multiple(rng, matches));
+        owned.shuffle(rng);
+        let numbers = |numbers: &[u32]| {
+            numbers
+                .iter()
+                .map(|n| format!("{:>2}", n))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        writeln!(
+            input,
+            "Card {:>3}: {} | {}",
+            index + 1,
+            numbers(&winning),
+            numbers(&owned)
+        )
+        .unwrap();
+    }
+    input
+}
+
+/// `size` seed ranges, short enough for the brute force of part 2, and maps
+/// whose source ranges never overlap.
+fn almanac(rng: &mut ChaCha8Rng, size: usize) -> String {
+    const MAX: u64 = 1 << 32;
+    let seeds: Vec<String> = (0..size)
+        .map(|_| format!("{} {}", rng.gen_range(0..MAX), rng.gen_range(1..=1000)))
+        .collect();
+    let mut input = format!("seeds: {}\n", seeds.join(" "));
+    for name in MAPS {
+        let mut bounds: Vec<u64> = (0..2 * rng.gen_range(1..=size.min(20)))
+            .map(|_| rng.gen_range(0..MAX))
+            .collect();
+        bounds.sort();
+        bounds.dedup();
+        write!(input, "\n{} map:\n", name).unwrap();
+        for range in bounds.chunks_exact(2) {
+            let length = range[1] - range[0];
+            let destination = rng.gen_range(0..MAX - length);
+            writeln!(input, "{} {} {}", destination, range[0], length).unwrap();
+        }
+    }
+    input
+}
+
+/// `size` races, at most four so that the single race of part 2 fits in a
+/// `u64`, each of them winnable.
+fn races(rng: &mut ChaCha8Rng, size: usize) -> String {
+    loop {
+        let times: Vec<u64> = (0..size).map(|_| rng.gen_range(10..100)).collect();
+        let distances: Vec<u64> = times
+            .iter()
+            .map(|time| rng.gen_range(1..(time / 2) * (time - time / 2)).min(999))
+            .collect();
+        let join = |values: &[u64]| {
+            values
+                .iter()
+                .map(|value| format!("{:>4}", value))
+                .collect::<String>()
+        };
+        let concat = |values: &[u64]| {
+            values
+                .iter()
+                .map(u64::to_string)
+                .collect::<String>()
+                .parse::<u128>()
+                .unwrap()
+        };
+        let (time, distance) = (concat(&times), concat(&distances));
+        if distance < (time / 2) * (time - time / 2) {
+            return format!("Time:    {}\nDistance:{}\n", join(&times), join(&distances));
+        }
+    }
+}
+
+fn hands(rng: &mut ChaCha8Rng, size: usize) -> String {
+    let cards: Vec<char> = "AKQJT98765432".chars().collect();
+    let mut input = String::new();
+    for _ in 0..size {
+        let hand: String = (0..5).map(|_| *cards.choose(rng).unwrap()).collect();
+        writeln!(input, "{} {}", hand, rng.gen_range(1..=1000)).unwrap();
+    }
+    input
+}
+
+/// Three letter node name, which ends in A or Z only when asked to.
+fn node_name(rng: &mut ChaCha8Rng, last: Option<char>, used: &mut HashSet<String>) -> String {
+    loop {
+        let last = last.unwrap_or_else(|| rng.gen_range('B'..='Y'));
+        let name: String = (0..2)
+            .map(|_| rng.gen_range('A'..='Z'))
+            .chain([last])
+            .collect();
+        if used.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+const PRIMES: [usize; 6] = [2, 3, 5, 7, 11, 13];
+
+/// Longest instructions of a network, whose ghosts walk loops of a prime
+/// multiple of their length: even with every prime, the nodes stay well
+/// inside the 26^3 possible names.
+const MAX_STEPS: usize = {
+    let (mut sum, mut index) = (0, 0);
+    while index < PRIMES.len() {
+        sum += PRIMES[index];
+        index += 1;
+    }
+    12_000 / sum
+};
+
+/// Instructions of `size` steps and one loop per ghost. Each ghost reaches
+/// its Z node after a prime multiple of the instruction length and then
+/// loops back to the node after its A node, so that it is on the Z node
+/// exactly at every multiple of that count. The first ghost walks from AAA
+/// to ZZZ.
+fn network(rng: &mut ChaCha8Rng, size: usize) -> String {
+    let mut primes = PRIMES.to_vec();
+    primes.shuffle(rng);
+    primes.truncate(rng.gen_range(1..=6));
+    let steps = size;
+    let instructions: Vec<char> = (0..steps)
+        .map(|_| *['L', 'R'].choose(rng).unwrap())
+        .collect();
+
+    let mut used = HashSet::from(["AAA".to_string(), "ZZZ".to_string()]);
+    let mut nodes: Vec<(String, [String; 2])> = vec![];
+    for (ghost, prime) in primes.iter().enumerate() {
+        let length = prime * steps;
+        let mut path = vec![match ghost {
+            0 => "AAA".to_string(),
+            _ => node_name(rng, Some('A'), &mut used),
+        }];
+        for _ in 1..length {
+            path.push(node_name(rng, None, &mut used));
+        }
+        path.push(match ghost {
+            0 => "ZZZ".to_string(),
+            _ => node_name(rng, Some('Z'), &mut used),
+        });
+
+        for (step, node) in path.iter().enumerate() {
+            let next = if step == length {
+                &path[1]
+            } else {
+                &path[step + 1]
+            };
+            // The direction not taken leads anywhere on the path except
+            // the Z node, which must only be reached on schedule.
+            let other = path[rng.gen_range(0..length)].clone();
+            let targets = match instructions[step % steps] {
+                'L' => [next.clone(), other],
+                _ => [other, next.clone()],
+            };
+            nodes.push((node.clone(), targets));
+        }
+    }
+    nodes.shuffle(rng);
+
+    let mut input: String = instructions.iter().collect();
+    input.push_str("\n\n");
+    for (node, [left, right]) in nodes {
+        writeln!(input, "{} = ({}, {})", node, left, right).unwrap();
+    }
+    input
+}
+
+/// Values of a polynomial with small coefficients, so that the differences
+/// reach zero before running out of values.
+fn histories(rng: &mut ChaCha8Rng, size: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let length = rng.gen_range(6..=21);
+        let coefficients: Vec<i64> = (0..rng.gen_range(1..=5.min(length - 1)))
+            .map(|_| rng.gen_range(-10..=10))
+            .collect();
+        let values: Vec<String> = (0..length as i64)
+            .map(|x| {
+                coefficients
+                    .iter()
+                    .rev()
+                    .fold(0, |value, coefficient| value * x + coefficient)
+                    .to_string()
+            })
+            .collect();
+        input.push_str(&values.join(" "));
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::{self, Solution};
+
+    #[test]
+    fn deterministic_by_seed() {
+        for day in days() {
+            let size = max_size(day).unwrap().min(20);
+            assert_eq!(generate(day, 7, size), generate(day, 7, size));
+            assert_ne!(generate(day, 7, size), generate(day, 8, size));
+        }
+        assert_eq!(generate(99, 7, 20), Err(GenerateError::NoGenerator(99)));
+    }
+
+    #[test]
+    fn sizes_are_honoured() {
+        let input = generate(6, 7, 3).unwrap();
+        let (times, _) = crate::parse::parse_all(days::day6::parse_input, &input).unwrap();
+        assert_eq!(times.len(), 3);
+        let input = generate(8, 7, MAX_STEPS).unwrap();
+        assert_eq!(input.lines().next().unwrap().len(), MAX_STEPS);
+        assert_eq!(generate(1, 7, 0), Err(GenerateError::Empty(1)));
+        assert_eq!(
+            generate(6, 7, 5),
+            Err(GenerateError::TooLarge {
+                day: 6,
+                size: 5,
+                max: 4
+            })
+        );
+    }
+
+    #[test]
+    fn generated_inputs_are_solvable() {
+        for day in days() {
+            let solution = days::get(day).unwrap();
+            for seed in 0..5 {
+                let input = generate(day, seed, max_size(day).unwrap().min(30)).unwrap();
+                for part in [1, 2] {
+                    if let Err(err) = solution.solve(part, &input) {
+                        assert!(
+                            err.is::<days::Unsolved>(),
+                            "day {} part {} seed {}: {}\n{}",
+                            day,
+                            part,
+                            seed,
+                            err,
+                            input
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn network_loops_on_schedule() {
+        let input = generate(8, 3, 10).unwrap();
+        let steps = input.lines().next().unwrap().len();
+        let answer: u64 = days::day8::Day8.part2(&input).unwrap().parse().unwrap();
+        assert_eq!(answer % steps as u64, 0);
+    }
+}
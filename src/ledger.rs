@@ -0,0 +1,241 @@
+//! Local record of every answer sent to the server and what it said about
+//! it, used to avoid submitting answers that are already known to be wrong.
+//!
+//! ```toml
+//! [[submission]]
+//! day = 5
+//! part = 1
+//! answer = "251346198"
+//! verdict = "correct"
+//! time = 1701766800
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint about the direction.
+    Wrong,
+    /// Rejected because the previous answer was sent too recently; says
+    /// nothing about the answer itself.
+    Wait,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::Wait => write!(f, "submitted too recently, try again later"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Submission {
+    pub day: u32,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+}
+
+#[derive(Debug)]
+pub enum LedgerError {
+    Io(PathBuf, io::Error),
+    Toml(PathBuf, String),
+}
+
+impl fmt::Display for LedgerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LedgerError::Io(path, err) => write!(f, "cannot access {}: {}", path.display(), err),
+            LedgerError::Toml(path, err) => write!(f, "invalid {}: {}", path.display(), err),
+        }
+    }
+}
+
+impl std::error::Error for LedgerError {}
+
+/// Why an answer is not worth sending.
+#[derive(Debug, PartialEq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    KnownWrong(Verdict),
+    /// The answer is at least an answer the server called too high.
+    TooHigh(String),
+    /// The answer is at most an answer the server called too low.
+    TooLow(String),
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => write!(f, "already solved with {}", answer),
+            Refusal::KnownWrong(verdict) => {
+                write!(f, "this answer was already submitted and is {}", verdict)
+            }
+            Refusal::TooHigh(bound) => write!(f, "{} was already too high", bound),
+            Refusal::TooLow(bound) => write!(f, "{} was already too low", bound),
+        }
+    }
+}
+
+impl std::error::Error for Refusal {}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Ledger {
+    #[serde(default, rename = "submission")]
+    pub submissions: Vec<Submission>,
+}
+
+impl Ledger {
+    /// Reads the ledger at `path`, which is empty if the file does not exist.
+    pub fn load(path: &Path) -> Result<Self, LedgerError> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(LedgerError::Io(path.to_path_buf(), err)),
+        };
+        toml::from_str(&content)
+            .map_err(|err| LedgerError::Toml(path.to_path_buf(), err.to_string()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), LedgerError> {
+        let content = toml::to_string(self)
+            .map_err(|err| LedgerError::Toml(path.to_path_buf(), err.to_string()))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| LedgerError::Io(parent.to_path_buf(), err))?;
+        }
+        fs::write(path, content).map_err(|err| LedgerError::Io(path.to_path_buf(), err))
+    }
+
+    pub fn record(&mut self, day: u32, part: u8, answer: &str, verdict: Verdict) {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        self.submissions.push(Submission {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            time,
+        });
+    }
+
+    fn submissions_of(&self, day: u32, part: u8) -> impl Iterator<Item = &Submission> {
+        self.submissions
+            .iter()
+            .filter(move |submission| submission.day == day && submission.part == part)
+    }
+
+    /// Checks `answer` against what the server already said about `day` and
+    /// `part`. Numeric answers are also compared with the known bounds.
+    pub fn check(&self, day: u32, part: u8, answer: &str) -> Result<(), Refusal> {
+        for submission in self.submissions_of(day, part) {
+            match submission.verdict {
+                Verdict::Correct => return Err(Refusal::AlreadySolved(submission.answer.clone())),
+                Verdict::Wait => {}
+                verdict if submission.answer == answer => return Err(Refusal::KnownWrong(verdict)),
+                _ => {}
+            }
+        }
+
+        let Ok(value) = answer.parse::<i64>() else {
+            return Ok(());
+        };
+        for submission in self.submissions_of(day, part) {
+            let Ok(bound) = submission.answer.parse::<i64>() else {
+                continue;
+            };
+            match submission.verdict {
+                Verdict::TooHigh if value >= bound => {
+                    return Err(Refusal::TooHigh(submission.answer.clone()))
+                }
+                Verdict::TooLow if value <= bound => {
+                    return Err(Refusal::TooLow(submission.answer.clone()))
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ledger(submissions: &[(u8, &str, Verdict)]) -> Ledger {
+        let mut ledger = Ledger::default();
+        for (part, answer, verdict) in submissions {
+            ledger.record(1, *part, answer, *verdict);
+        }
+        ledger
+    }
+
+    #[test]
+    fn known_bounds_are_refused() {
+        let ledger = ledger(&[
+            (1, "100", Verdict::TooHigh),
+            (1, "10", Verdict::TooLow),
+            (1, "50", Verdict::Wrong),
+            (1, "60", Verdict::Wait),
+        ]);
+        assert_eq!(
+            ledger.check(1, 1, "100"),
+            Err(Refusal::KnownWrong(Verdict::TooHigh))
+        );
+        assert_eq!(
+            ledger.check(1, 1, "120"),
+            Err(Refusal::TooHigh("100".to_string()))
+        );
+        assert_eq!(
+            ledger.check(1, 1, "-3"),
+            Err(Refusal::TooLow("10".to_string()))
+        );
+        assert_eq!(
+            ledger.check(1, 1, "50"),
+            Err(Refusal::KnownWrong(Verdict::Wrong))
+        );
+        assert_eq!(ledger.check(1, 1, "60"), Ok(()));
+        assert_eq!(ledger.check(1, 1, "99"), Ok(()));
+        assert_eq!(ledger.check(1, 1, "abc"), Ok(()));
+        assert_eq!(ledger.check(1, 2, "120"), Ok(()));
+        assert_eq!(ledger.check(2, 1, "120"), Ok(()));
+    }
+
+    #[test]
+    fn solved_parts_are_refused() {
+        let ledger = ledger(&[(2, "42", Verdict::Correct)]);
+        assert_eq!(
+            ledger.check(1, 2, "43"),
+            Err(Refusal::AlreadySolved("42".to_string()))
+        );
+    }
+
+    #[test]
+    fn round_trips_through_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.toml");
+        assert_eq!(Ledger::load(&path).unwrap(), Ledger::default());
+
+        let ledger = ledger(&[(1, "7", Verdict::TooLow), (1, "9", Verdict::Correct)]);
+        ledger.save(&path).unwrap();
+        assert_eq!(Ledger::load(&path).unwrap(), ledger);
+    }
+}
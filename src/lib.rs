@@ -1,4 +1,21 @@
+pub mod answers;
+pub mod client;
+pub mod config;
+pub mod days;
+pub mod fuzz;
+pub mod generate;
+pub mod ledger;
+pub mod parse;
+pub mod reference;
+pub mod runner;
+pub mod scaffold;
+#[cfg(test)]
+mod stub_server;
+#[cfg(feature = "tui")]
+pub mod tui;
 pub mod utils;
+#[cfg(feature = "viz")]
+pub mod viz;
 
 pub fn add(left: usize, right: usize) -> usize {
     left + right
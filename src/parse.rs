@@ -0,0 +1,295 @@
+//! nom combinators shared by the days. Everything here works on complete
+//! input, so a parser reaching the end of the text fails instead of asking
+//! for more. Line breaks may be `\n` or `\r\n` and may be preceded by
+//! trailing spaces.
+
+use std::fmt;
+
+use nom::{
+    character::complete::{char, line_ending, multispace0, none_of, space0, space1},
+    combinator::{all_consuming, cut, fail, map},
+    error::{Error, ErrorKind},
+    multi::{count, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated},
+    Finish, IResult, Parser,
+};
+
+/// A parse failure pointing at the line and column (both 1-based) where the
+/// parser gave up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ErrorKind,
+}
+
+impl ParseError {
+    fn new(input: &str, remaining: &str, kind: ErrorKind) -> Self {
+        let consumed = &input[..input.len() - remaining.len()];
+        let line = consumed.matches('\n').count() + 1;
+        let column = consumed.len() - consumed.rfind('\n').map_or(0, |i| i + 1) + 1;
+        Self { line, column, kind }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "parse error at line {}, column {}: ",
+            self.line, self.column
+        )?;
+        match self.kind {
+            ErrorKind::Eof => write!(f, "unexpected trailing input"),
+            kind => write!(f, "expected {}", kind.description()),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Runs `parser` over the whole input, allowing only trailing whitespace
+/// after it.
+pub fn parse_all<'a, O, P>(parser: P, input: &'a str) -> Result<O, ParseError>
+where
+    P: Parser<&'a str, O, Error<&'a str>>,
+{
+    all_consuming(terminated(parser, multispace0))(input)
+        .finish()
+        .map(|(_, output)| output)
+        .map_err(|err| ParseError::new(input, err.input, err.code))
+}
+
+/// Space-separated values, e.g. `79 14 55 13`. A space must be followed by
+/// another value or by the end of the line, so that a bad value is reported
+/// where it is rather than at the end of the list.
+pub fn list<'a, O, F>(item: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    list_until(item, fail::<_, (), _>)
+}
+
+/// Like [`list`], but the values may also be followed by `end`, which is
+/// left for the next parser.
+pub fn list_until<'a, O, E, F, G>(
+    mut item: F,
+    mut end: G,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+    G: Parser<&'a str, E, Error<&'a str>>,
+{
+    move |input| {
+        let (mut input, first) = item.parse(input)?;
+        let mut items = vec![first];
+        while let Ok((rest, _)) = space1::<_, Error<&str>>(input) {
+            if rest.is_empty() || rest.starts_with(['\r', '\n']) || end.parse(rest).is_ok() {
+                break;
+            }
+            let (rest, next) = cut(|input| item.parse(input))(rest)?;
+            items.push(next);
+            input = rest;
+        }
+        Ok((input, items))
+    }
+}
+
+/// Space-separated unsigned numbers.
+pub fn u64s(input: &str) -> IResult<&str, Vec<u64>> {
+    list(nom::character::complete::u64)(input)
+}
+
+/// Space-separated signed numbers.
+pub fn i64s(input: &str) -> IResult<&str, Vec<i64>> {
+    list(nom::character::complete::i64)(input)
+}
+
+/// `label: value`, with any amount of spaces after the colon.
+pub fn labelled<'a, L, V, F, G>(
+    label: F,
+    value: G,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (L, V)>
+where
+    F: Parser<&'a str, L, Error<&'a str>>,
+    G: Parser<&'a str, V, Error<&'a str>>,
+{
+    separated_pair(label, pair(char(':'), space0), value)
+}
+
+/// End of a line, ignoring any trailing spaces before it.
+pub fn line_end(input: &str) -> IResult<&str, &str> {
+    preceded(space0, line_ending)(input)
+}
+
+/// One `item` per line.
+pub fn lines<'a, O, F>(item: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(line_end, item)
+}
+
+/// The empty line between two sections, including the end of the line
+/// before it.
+pub fn blank_line(input: &str) -> IResult<&str, ()> {
+    map(count(line_end, 2), |_| ())(input)
+}
+
+/// Sections separated by blank lines.
+pub fn sections<'a, O, F>(section: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(blank_line, section)
+}
+
+/// A `header:` line followed by one `item` per line.
+pub fn block<'a, H, O, F, G>(
+    header: F,
+    item: G,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (H, Vec<O>)>
+where
+    F: Parser<&'a str, H, Error<&'a str>>,
+    G: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_pair(header, pair(char(':'), line_end), lines(item))
+}
+
+/// One `key = value` pair per line.
+pub fn key_values<'a, K, V, F, G>(
+    key: F,
+    value: G,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<(K, V)>>
+where
+    F: Parser<&'a str, K, Error<&'a str>>,
+    G: Parser<&'a str, V, Error<&'a str>>,
+{
+    lines(separated_pair(
+        key,
+        delimited(space0, char('='), space0),
+        value,
+    ))
+}
+
+/// Rows of characters up to the end of each line.
+pub fn grid(input: &str) -> IResult<&str, Vec<Vec<char>>> {
+    lines(many1(none_of("\r\n")))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{
+        bytes::complete::tag,
+        character::complete::{alpha1, u32},
+    };
+
+    #[test]
+    fn numbers() {
+        assert_eq!(u64s("79 14  55 13"), Ok(("", vec![79, 14, 55, 13])));
+        assert_eq!(i64s("0 -3 6"), Ok(("", vec![0, -3, 6])));
+        assert_eq!(u64s("1 2\n3"), Ok(("\n3", vec![1, 2])));
+        assert!(u64s("").is_err());
+    }
+
+    #[test]
+    fn lists_up_to_an_end() {
+        assert_eq!(
+            list_until(u32, tag("|"))("41 48 | 83"),
+            Ok((" | 83", vec![41, 48]))
+        );
+        assert!(matches!(
+            list_until(u32, tag("|"))("41 x | 83"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+
+    #[test]
+    fn labelled_line() {
+        assert_eq!(
+            labelled(tag("Time"), u64s)("Time:      7  15   30"),
+            Ok(("", ("Time", vec![7, 15, 30])))
+        );
+        assert_eq!(
+            labelled(tag("seeds"), u64s)("seeds: 79 14"),
+            Ok(("", ("seeds", vec![79, 14])))
+        );
+    }
+
+    #[test]
+    fn lines_and_sections() {
+        assert_eq!(
+            sections(lines(u64s))("1 2\n3\n\n4\r\n\r\n5 6"),
+            Ok((
+                "",
+                vec![vec![vec![1, 2], vec![3]], vec![vec![4]], vec![vec![5, 6]]]
+            ))
+        );
+    }
+
+    #[test]
+    fn line_endings_and_trailing_spaces() {
+        assert_eq!(
+            parse_all(lines(u64s), "1 2 \r\n3\t\r\n4"),
+            Ok(vec![vec![1, 2], vec![3], vec![4]])
+        );
+        assert_eq!(
+            parse_all(sections(lines(u64s)), "1 \r\n  \r\n2 \n\n3 \n \n"),
+            Ok(vec![vec![vec![1]], vec![vec![2]], vec![vec![3]]])
+        );
+    }
+
+    #[test]
+    fn blocks() {
+        assert_eq!(
+            block(tag("seed-to-soil map"), u64s)("seed-to-soil map:\n50 98 2\n52 50 48\n"),
+            Ok((
+                "\n",
+                ("seed-to-soil map", vec![vec![50, 98, 2], vec![52, 50, 48]])
+            ))
+        );
+    }
+
+    #[test]
+    fn key_value_lines() {
+        assert_eq!(
+            key_values(alpha1, u32)("a = 1\nbc=2"),
+            Ok(("", vec![("a", 1), ("bc", 2)]))
+        );
+    }
+
+    #[test]
+    fn grids() {
+        assert_eq!(
+            grid("#.\r\n.#\n"),
+            Ok(("\n", vec![vec!['#', '.'], vec!['.', '#']]))
+        );
+    }
+
+    #[test]
+    fn errors_point_at_line_and_column() {
+        assert_eq!(
+            parse_all(lines(u64s), "1 2\n3 4\n\n"),
+            Ok(vec![vec![1, 2], vec![3, 4]])
+        );
+
+        let err = parse_all(lines(u64s), "1 2\n3 x\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(
+            err.to_string(),
+            "parse error at line 2, column 3: expected Digit"
+        );
+
+        // The bad value is reported, not the end of the list before it.
+        let err = parse_all(lines(u64s), "1 2\n3 4 x 5\n6\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.kind), (2, 5, ErrorKind::Digit));
+        assert_eq!(
+            parse_all(lines(u64s), "1 2  \n3 \n"),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+
+        let err = parse_all(labelled(tag("Time"), u64s), "Tim: 1").unwrap_err();
+        assert_eq!((err.line, err.column, err.kind), (1, 1, ErrorKind::Tag));
+    }
+}
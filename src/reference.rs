@@ -0,0 +1,736 @@
+//! Naive solutions that trade speed for obviousness, and a harness that
+//! compares them with the real solutions on generated inputs.
+//!
+//! Each reference parses its input on its own and solves it the slow way:
+//! simulating every hold time instead of solving a quadratic, walking the
+//! ghosts in lock step instead of taking an LCM, and so on. They are only
+//! meant for the small inputs of the harness.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+
+use crate::days::{self, Error, Solution, Unsolved};
+use crate::generate::{self, GenerateError};
+
+fn numbers<T: std::str::FromStr>(text: &str) -> Result<Vec<T>, Error>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    Ok(text
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<_, _>>()?)
+}
+
+/// Text after the `:` of a labelled line.
+fn after_colon(line: &str) -> Result<&str, Error> {
+    Ok(line
+        .split_once(':')
+        .ok_or_else(|| format!("no ':' in '{}'", line))?
+        .1)
+}
+
+pub struct Day1;
+
+impl Day1 {
+    fn sum(input: &str, words: bool) -> Result<String, Error> {
+        const WORDS: [&str; 9] = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+        let mut sum = 0;
+        for line in input.lines() {
+            let mut digits = vec![];
+            for (start, _) in line.char_indices() {
+                let rest = &line[start..];
+                if let Some(digit) = rest.chars().next().and_then(|c| c.to_digit(10)) {
+                    digits.push(digit);
+                } else if words {
+                    if let Some(index) = WORDS.iter().position(|word| rest.starts_with(word)) {
+                        digits.push(index as u32 + 1);
+                    }
+                }
+            }
+            match (digits.first(), digits.last()) {
+                (Some(first), Some(last)) => sum += first * 10 + last,
+                _ => return Err(format!("no digit in '{}'", line).into()),
+            }
+        }
+        Ok(sum.to_string())
+    }
+}
+
+impl Solution for Day1 {
+    fn part1(&self, input: &str) -> Result<String, Error> {
+        Self::sum(input, false)
+    }
+
+    fn part2(&self, input: &str) -> Result<String, Error> {
+        Self::sum(input, true)
+    }
+}
+
+pub struct Day2;
+
+impl Day2 {
+    /// Id and largest count of red, green and blue of every game.
+    fn games(input: &str) -> Result<Vec<(u32, [u32; 3])>, Error> {
+        let mut games = vec![];
+        for line in input.lines() {
+            let (game, rounds) = line.split_once(": ").ok_or("no ': '")?;
+            let id = game.trim_start_matches("Game ").parse()?;
+            let mut most = [0; 3];
+            for cube in rounds.split([';', ',']) {
+                let (amount, color) = cube.trim().split_once(' ').ok_or("no ' '")?;
+                let index = ["red", "green", "blue"]
+                    .iter()
+                    .position(|known| *known == color)
+                    .ok_or_else(|| format!("unknown colour '{}'", color))?;
+                most[index] = most[index].max(amount.parse()?);
+            }
+            games.push((id, most));
+        }
+        Ok(games)
+    }
+}
+
+impl Solution for Day2 {
+    fn part1(&self, input: &str) -> Result<String, Error> {
+        let possible = Self::games(input)?
+            .into_iter()
+            .filter(|(_, [red, green, blue])| *red <= 12 && *green <= 13 && *blue <= 14);
+        Ok(possible.map(|(id, _)| id).sum::<u32>().to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, Error> {
+        let powers = Self::games(input)?
+            .into_iter()
+            .map(|(_, most)| most.iter().product::<u32>());
+        Ok(powers.sum::<u32>().to_string())
+    }
+}
+
+pub struct Day3;
+
+impl Day3 {
+    /// Every number with the cells it covers.
+    fn numbers(grid: &[Vec<char>]) -> Vec<(u32, Vec<(usize, usize)>)> {
+        let mut numbers = vec![];
+        for (row, line) in grid.iter().enumerate() {
+            let mut column = 0;
+            while column < line.len() {
+                if !line[column].is_ascii_digit() {
+                    column += 1;
+                    continue;
+                }
+                let mut cells = vec![];
+                let mut value = 0;
+                while column < line.len() && line[column].is_ascii_digit() {
+                    value = value * 10 + line[column].to_digit(10).unwrap();
+                    cells.push((row, column));
+                    column += 1;
+                }
+                numbers.push((value, cells));
+            }
+        }
+        numbers
+    }
+
+    fn touches(cells: &[(usize, usize)], row: usize, column: usize) -> bool {
+        cells
+            .iter()
+            .any(|(r, c)| r.abs_diff(row) <= 1 && c.abs_diff(column) <= 1)
+    }
+
+    fn grid(input: &str) -> Vec<Vec<char>> {
+        input.lines().map(|line| line.chars().collect()).collect()
+    }
+}
+
+impl Solution for Day3 {
+    fn part1(&self, input: &str) -> Result<String, Error> {
+        let grid = Self::grid(input);
+        let mut sum = 0;
+        for (value, cells) in Self::numbers(&grid) {
+            let adjacent = grid.iter().enumerate().any(|(row, line)| {
+                line.iter().enumerate().any(|(column, c)| {
+                    *c != '.' && !c.is_ascii_digit() && Self::touches(&cells, row, column)
+                })
+            });
+            if adjacent {
+                sum += value;
+            }
+        }
+        Ok(sum.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, Error> {
+        let grid = Self::grid(input);
+        let numbers = Self::numbers(&grid);
+        let mut sum = 0;
+        for (row, line) in grid.iter().enumerate() {
+            for (column, c) in line.iter().enumerate() {
+                if *c != '*' {
+                    continue;
+                }
+                let adjacent: Vec<u32> = numbers
+                    .iter()
+                    .filter(|(_, cells)| Self::touches(cells, row, column))
+                    .map(|(value, _)| *value)
+                    .collect();
+                if adjacent.len() == 2 {
+                    sum += adjacent[0] * adjacent[1];
+                }
+            }
+        }
+        Ok(sum.to_string())
+    }
+}
+
+pub struct Day4;
+
+impl Day4 {
+    fn matches(input: &str) -> Result<Vec<usize>, Error> {
+        let mut matches = vec![];
+        for line in input.lines() {
+            let (winning, owned) = after_colon(line)?.split_once('|').ok_or("no '|'")?;
+            let winning: Vec<u32> = numbers(winning)?;
+            let owned: Vec<u32> = numbers(owned)?;
+            matches.push(owned.iter().filter(|n| winning.contains(n)).count());
+        }
+        Ok(matches)
+    }
+}
+
+impl Solution for Day4 {
+    fn part1(&self, input: &str) -> Result<String, Error> {
+        let points =
+            Self::matches(input)?
+                .into_iter()
+                .map(|n| if n == 0 { 0 } else { 1u64 << (n - 1) });
+        Ok(points.sum::<u64>().to_string())
+    }
+
+    /// Counts the cards each card ends up producing, from the last card
+    /// backwards, instead of pushing copies forward.
+    fn part2(&self, input: &str) -> Result<String, Error> {
+        let matches = Self::matches(input)?;
+        let mut produced = vec![0u64; matches.len()];
+        for index in (0..matches.len()).rev() {
+            let last = (index + matches[index]).min(matches.len() - 1);
+            produced[index] = 1 + produced[index + 1..=last].iter().sum::<u64>();
+        }
+        Ok(produced.iter().sum::<u64>().to_string())
+    }
+}
+
+pub struct Day5;
+
+impl Day5 {
+    fn lowest(input: &str, seeds: impl Iterator<Item = u64>) -> Result<String, Error> {
+        let mut maps: Vec<Vec<Vec<u64>>> = vec![];
+        for block in input.split("\n\n").skip(1) {
+            maps.push(
+                block
+                    .lines()
+                    .skip(1)
+                    .map(numbers)
+                    .collect::<Result<_, _>>()?,
+            );
+        }
+        let mut lowest = None;
+        for mut value in seeds {
+            for map in &maps {
+                if let Some(range) = map
+                    .iter()
+                    .find(|range| range[1] <= value && value < range[1] + range[2])
+                {
+                    value = range[0] + (value - range[1]);
+                }
+            }
+            lowest = Some(lowest.map_or(value, |lowest: u64| lowest.min(value)));
+        }
+        Ok(lowest.ok_or("no seeds")?.to_string())
+    }
+
+    fn seeds(input: &str) -> Result<Vec<u64>, Error> {
+        numbers(after_colon(input.lines().next().unwrap_or_default())?)
+    }
+}
+
+impl Solution for Day5 {
+    fn part1(&self, input: &str) -> Result<String, Error> {
+        Self::lowest(input, Self::seeds(input)?.into_iter())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, Error> {
+        let seeds = Self::seeds(input)?;
+        let ranges = seeds.chunks(2).flat_map(|pair| pair[0]..pair[0] + pair[1]);
+        Self::lowest(input, ranges)
+    }
+}
+
+pub struct Day6;
+
+impl Day6 {
+    fn ways(time: u64, distance: u64) -> u64 {
+        (0..=time)
+            .filter(|hold| hold * (time - hold) > distance)
+            .count() as u64
+    }
+
+    fn lines(input: &str) -> Result<(&str, &str), Error> {
+        let mut lines = input.lines();
+        let times = after_colon(lines.next().unwrap_or_default())?;
+        let distances = after_colon(lines.next().unwrap_or_default())?;
+        Ok((times, distances))
+    }
+}
+
+impl Solution for Day6 {
+    fn part1(&self, input: &str) -> Result<String, Error> {
+        let (times, distances) = Self::lines(input)?;
+        let times: Vec<u64> = numbers(times)?;
+        let distances: Vec<u64> = numbers(distances)?;
+        let ways = times
+            .iter()
+            .zip(&distances)
+            .map(|(t, d)| Self::ways(*t, *d));
+        Ok(ways.product::<u64>().to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, Error> {
+        let (times, distances) = Self::lines(input)?;
+        let join = |text: &str| text.split_whitespace().collect::<String>().parse::<u64>();
+        Ok(Self::ways(join(times)?, join(distances)?).to_string())
+    }
+}
+
+pub struct Day7;
+
+impl Day7 {
+    /// Strength of the hand's type, higher is better.
+    fn strength(hand: &str) -> usize {
+        let mut counts: Vec<usize> = hand
+            .chars()
+            .map(|card| hand.chars().filter(|other| *other == card).count())
+            .collect();
+        counts.sort();
+        counts.reverse();
+        match counts[..] {
+            [5, ..] => 6,
+            [4, ..] => 5,
+            [3, 3, 3, 2, 2] => 4,
+            [3, ..] => 3,
+            [2, 2, 2, 2, 1] => 2,
+            [2, ..] => 1,
+            _ => 0,
+        }
+    }
+
+    fn winnings(input: &str, jokers: bool) -> Result<String, Error> {
+        let order = if jokers {
+            "J23456789TQKA"
+        } else {
+            "23456789TJQKA"
+        };
+        let mut hands = vec![];
+        for line in input.lines() {
+            let (hand, bid) = line.split_once(' ').ok_or("no ' '")?;
+            let strength = if jokers {
+                // Turning every joker into the same card is always best.
+                order
+                    .chars()
+                    .map(|card| Self::strength(&hand.replace('J', &card.to_string())))
+                    .max()
+                    .unwrap()
+            } else {
+                Self::strength(hand)
+            };
+            let cards: Vec<usize> = hand.chars().map(|c| order.find(c).unwrap_or(0)).collect();
+            hands.push((strength, cards, bid.trim().parse::<u32>()?));
+        }
+        hands.sort();
+        let winnings = hands
+            .iter()
+            .enumerate()
+            .map(|(rank, (_, _, bid))| (rank as u64 + 1) * *bid as u64);
+        Ok(winnings.sum::<u64>().to_string())
+    }
+}
+
+impl Solution for Day7 {
+    fn part1(&self, input: &str) -> Result<String, Error> {
+        Self::winnings(input, false)
+    }
+
+    fn part2(&self, input: &str) -> Result<String, Error> {
+        Self::winnings(input, true)
+    }
+}
+
+pub struct Day8;
+
+impl Day8 {
+    /// Steps after which every ghost stands on a node accepted by `end`,
+    /// found by walking all of them one step at a time.
+    fn walk(
+        input: &str,
+        start: impl Fn(&str) -> bool,
+        end: impl Fn(&str) -> bool,
+    ) -> Result<String, Error> {
+        const GIVE_UP: usize = 100_000_000;
+        let (instructions, nodes) = input.split_once("\n\n").ok_or("no blank line")?;
+        let mut network = HashMap::new();
+        for line in nodes.lines() {
+            let (node, targets) = line.split_once(" = ").ok_or("no ' = '")?;
+            let (left, right) = targets
+                .trim_matches(['(', ')'])
+                .split_once(", ")
+                .ok_or("no ', '")?;
+            if network.insert(node, (left, right)).is_some() {
+                return Err("duplicate node".into());
+            }
+        }
+
+        let mut ghosts: Vec<&str> = network.keys().copied().filter(|node| start(node)).collect();
+        if ghosts.is_empty() {
+            return Err("no starting node".into());
+        }
+        for (steps, instruction) in instructions.trim().chars().cycle().enumerate() {
+            if ghosts.iter().all(|node| end(node)) {
+                return Ok(steps.to_string());
+            }
+            if steps == GIVE_UP {
+                break;
+            }
+            for node in ghosts.iter_mut() {
+                let (left, right) = network.get(node).ok_or("unknown node")?;
+                *node = if instruction == 'L' { left } else { right };
+            }
+        }
+        Err("the ghosts never meet".into())
+    }
+}
+
+impl Solution for Day8 {
+    fn part1(&self, input: &str) -> Result<String, Error> {
+        Self::walk(input, |node| node == "AAA", |node| node == "ZZZ")
+    }
+
+    fn part2(&self, input: &str) -> Result<String, Error> {
+        Self::walk(
+            input,
+            |node| node.ends_with('A'),
+            |node| node.ends_with('Z'),
+        )
+    }
+}
+
+pub struct Day9;
+
+impl Day9 {
+    /// Rows of differences down to the first row of zeros.
+    fn table(line: &str) -> Result<Vec<Vec<i64>>, Error> {
+        let mut table = vec![numbers::<i64>(line)?];
+        while table.last().unwrap().iter().any(|value| *value != 0) {
+            let row = table.last().unwrap();
+            let next = (1..row.len()).map(|i| row[i] - row[i - 1]).collect();
+            table.push(next);
+        }
+        Ok(table)
+    }
+}
+
+impl Solution for Day9 {
+    fn part1(&self, input: &str) -> Result<String, Error> {
+        let mut sum = 0;
+        for line in input.lines() {
+            let mut next = 0;
+            for row in Self::table(line)?.iter().rev() {
+                next += row.last().copied().unwrap_or(0);
+            }
+            sum += next;
+        }
+        Ok(sum.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, Error> {
+        let mut sum = 0;
+        for line in input.lines() {
+            let mut previous = 0;
+            for row in Self::table(line)?.iter().rev() {
+                previous = row.first().copied().unwrap_or(0) - previous;
+            }
+            sum += previous;
+        }
+        Ok(sum.to_string())
+    }
+}
+
+static REFERENCES: &[(u32, &dyn Solution)] = &[
+    (1, &Day1),
+    (2, &Day2),
+    (3, &Day3),
+    (4, &Day4),
+    (5, &Day5),
+    (6, &Day6),
+    (7, &Day7),
+    (8, &Day8),
+    (9, &Day9),
+];
+
+/// Reference solution of `day`, if there is one.
+pub fn get(day: u32) -> Option<&'static dyn Solution> {
+    REFERENCES
+        .iter()
+        .find(|(solved, _)| *solved == day)
+        .map(|(_, solution)| *solution)
+}
+
+/// Generated input on which a solution and its reference disagree.
+#[derive(Debug)]
+pub struct Disagreement {
+    pub day: u32,
+    pub part: u8,
+    pub seed: u64,
+    pub size: usize,
+    pub input: String,
+    pub reference: Result<String, String>,
+    pub solution: Result<String, String>,
+}
+
+impl fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let show = |answer: &Result<String, String>| match answer {
+            Ok(answer) => answer.clone(),
+            Err(err) => format!("error: {}", err),
+        };
+        writeln!(
+            f,
+            "day {} part {} disagrees on seed {} size {}",
+            self.day, self.part, self.seed, self.size
+        )?;
+        writeln!(f, "  reference: {}", show(&self.reference))?;
+        writeln!(f, "  solution:  {}", show(&self.solution))?;
+        write!(f, "{}", self.input)
+    }
+}
+
+/// Why a day could not be compared at all.
+#[derive(Debug, PartialEq)]
+pub enum CompareError {
+    NoSolution(u32),
+    NoReference(u32),
+    Generate(GenerateError),
+}
+
+impl fmt::Display for CompareError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompareError::NoSolution(day) => write!(f, "there is no solution for day {}", day),
+            CompareError::NoReference(day) => write!(f, "there is no reference for day {}", day),
+            CompareError::Generate(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for CompareError {}
+
+impl From<GenerateError> for CompareError {
+    fn from(err: GenerateError) -> Self {
+        CompareError::Generate(err)
+    }
+}
+
+/// Compares the solution of `day` with its reference on generated inputs.
+pub fn differential(
+    day: u32,
+    seeds: Range<u64>,
+    sizes: Range<usize>,
+) -> Result<Option<Disagreement>, CompareError> {
+    let solution = days::get(day).ok_or(CompareError::NoSolution(day))?;
+    let reference = get(day).ok_or(CompareError::NoReference(day))?;
+    compare(day, solution, reference, seeds, sizes)
+}
+
+/// Solves generated inputs of `day` with both solutions, trying every seed
+/// at a size before moving to the next one, and returns the shortest input
+/// of the smallest size on which they disagree. Parts the solution has not
+/// solved are skipped, and inputs both of them reject count as agreement.
+/// Sizes the generator cannot produce are an error rather than a pass.
+pub fn compare(
+    day: u32,
+    solution: &dyn Solution,
+    reference: &dyn Solution,
+    seeds: Range<u64>,
+    sizes: Range<usize>,
+) -> Result<Option<Disagreement>, CompareError> {
+    for size in sizes {
+        let mut smallest: Option<Disagreement> = None;
+        for seed in seeds.clone() {
+            let input = generate::generate(day, seed, size)?;
+            for part in [1, 2] {
+                let found = solution.solve(part, &input);
+                if matches!(&found, Err(err) if err.is::<Unsolved>()) {
+                    continue;
+                }
+                let expected = reference.solve(part, &input);
+                let agree = match (&expected, &found) {
+                    (Ok(expected), Ok(found)) => expected == found,
+                    (Err(_), Err(_)) => true,
+                    _ => false,
+                };
+                let shorter = smallest
+                    .as_ref()
+                    .is_none_or(|smallest| input.len() < smallest.input.len());
+                if !agree && shorter {
+                    smallest = Some(Disagreement {
+                        day,
+                        part,
+                        seed,
+                        size,
+                        input: input.clone(),
+                        reference: expected.map_err(|err| err.to_string()),
+                        solution: found.map_err(|err| err.to_string()),
+                    });
+                }
+            }
+        }
+        if smallest.is_some() {
+            return Ok(smallest);
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Largest size to compare `day` on. Past three races the naive day 6
+    /// takes seconds, as do the naive day 8 on longer instructions and the
+    /// naive day 3 on larger grids.
+    fn max_size(day: u32) -> usize {
+        match day {
+            6 => 3,
+            8 => 4,
+            _ => 30,
+        }
+    }
+
+    #[test]
+    fn references_solve_the_examples() {
+        let examples = [
+            (1, include_str!("data/day1/examples/part1.txt"), 1, "142"),
+            (1, include_str!("data/day1/examples/part2.txt"), 2, "281"),
+            (2, include_str!("data/day2/examples/part1.txt"), 1, "8"),
+            (2, include_str!("data/day2/examples/part1.txt"), 2, "2286"),
+            (3, include_str!("data/day3/examples/part1.txt"), 1, "4361"),
+            (3, include_str!("data/day3/examples/part1.txt"), 2, "467835"),
+            (4, include_str!("data/day4/examples/part1.txt"), 1, "13"),
+            (4, include_str!("data/day4/examples/part1.txt"), 2, "30"),
+            (5, include_str!("data/day5/examples/part1.txt"), 1, "35"),
+            (5, include_str!("data/day5/examples/part1.txt"), 2, "46"),
+            (6, include_str!("data/day6/example"), 1, "288"),
+            (6, include_str!("data/day6/example"), 2, "71503"),
+            (7, include_str!("data/day7/example"), 1, "6440"),
+            (7, include_str!("data/day7/example"), 2, "5905"),
+            (8, include_str!("data/day8/example"), 2, "6"),
+            (9, include_str!("data/day9/example"), 1, "114"),
+            (9, include_str!("data/day9/example"), 2, "2"),
+        ];
+        for (day, input, part, expected) in examples {
+            assert_eq!(
+                get(day).unwrap().solve(part, input).unwrap(),
+                expected,
+                "day {} part {}",
+                day,
+                part
+            );
+        }
+    }
+
+    #[test]
+    fn non_ascii_lines() {
+        let input = "\u{e9}1two\u{3de}\n";
+        for (part, expected) in [(1, "11"), (2, "12")] {
+            assert_eq!(Day1.solve(part, input).unwrap(), expected);
+            assert_eq!(days::day1::Day1.solve(part, input).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn solutions_agree_with_references() {
+        // Every day is compared: one without a generator or a reference
+        // fails here rather than passing unchecked.
+        for day in days::days() {
+            let sizes = 1..max_size(day).min(12) + 1;
+            if let Some(disagreement) = differential(day, 0..16, sizes).unwrap() {
+                panic!("{}", disagreement);
+            }
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+        #[test]
+        fn solutions_agree_on_any_seed(seed in 0..u64::MAX, size in 1..=30usize) {
+            for day in days::days() {
+                let size = size.min(max_size(day));
+                let compared = differential(day, seed..seed + 1, size..size + 1);
+                prop_assert!(compared.is_ok(), "day {}: {}", day, compared.unwrap_err());
+                if let Ok(Some(disagreement)) = compared {
+                    prop_assert!(false, "{}", disagreement);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn smallest_disagreement_is_reported() {
+        /// Wrong on every input of three lines or more.
+        struct Broken;
+
+        impl Solution for Broken {
+            fn part1(&self, input: &str) -> Result<String, Error> {
+                days::day9::Day9.part1(input)
+            }
+
+            fn part2(&self, input: &str) -> Result<String, Error> {
+                match input.lines().count() {
+                    0..=2 => days::day9::Day9.part2(input),
+                    _ => Ok("wrong".to_string()),
+                }
+            }
+        }
+
+        let disagreement = compare(9, &Broken, &Day9, 0..4, 1..6).unwrap().unwrap();
+        assert_eq!((disagreement.part, disagreement.size), (2, 3));
+        assert_eq!(disagreement.solution, Ok("wrong".to_string()));
+        let shortest = (0..4)
+            .map(|seed| generate::generate(9, seed, 3).unwrap().len())
+            .min();
+        assert_eq!(Some(disagreement.input.len()), shortest);
+        assert!(compare(9, &days::day9::Day9, &Day9, 0..4, 1..6)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn uncompared_days_are_errors() {
+        assert_eq!(
+            differential(99, 0..1, 1..2).unwrap_err(),
+            CompareError::NoSolution(99)
+        );
+        assert_eq!(
+            differential(6, 0..1, 5..6).unwrap_err(),
+            CompareError::Generate(GenerateError::TooLarge {
+                day: 6,
+                size: 5,
+                max: 4
+            })
+        );
+    }
+}
@@ -0,0 +1,522 @@
+//! Solves days on their inputs and compares the results with the accepted
+//! answers in each day's `answers.toml`.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+use rayon::prelude::*;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::answers::{Answers, AnswersError};
+use crate::config::{Config, INPUT};
+use crate::days::{self, Solution};
+use crate::utils;
+
+/// Result of solving one part of a day on one input.
+#[derive(Debug)]
+pub struct Outcome {
+    pub day: u32,
+    /// Input file name, relative to the day's directory.
+    pub input: String,
+    pub part: u8,
+    pub answer: Result<String, String>,
+    /// Accepted answer, if one has been locked.
+    pub expected: Option<String>,
+    pub elapsed: Duration,
+    /// Hex SHA-256 of the input file, if it could be read.
+    pub input_sha256: Option<String>,
+}
+
+impl Outcome {
+    /// Whether the answer differs from the accepted one. Parts without an
+    /// accepted answer never drift.
+    pub fn drifted(&self) -> bool {
+        match &self.expected {
+            Some(expected) => self.answer.as_ref() != Ok(expected),
+            None => false,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "day {} {} part {}: ", self.day, self.input, self.part)?;
+        match &self.answer {
+            Ok(answer) => write!(f, "{}", answer)?,
+            Err(err) => write!(f, "error: {}", err)?,
+        }
+        match &self.expected {
+            Some(expected) if self.drifted() => write!(f, " (DRIFTED, expected {})", expected)?,
+            Some(_) => write!(f, " (ok)")?,
+            None => {}
+        }
+        write!(f, " [{:.2?}]", self.elapsed)
+    }
+}
+
+/// Answers of several inputs of a day, printed as a table with one row per
+/// input and one column per part.
+pub struct Matrix<'a>(pub &'a [Outcome]);
+
+impl fmt::Display for Matrix<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut rows: Vec<[String; 3]> = vec![];
+        for outcome in self.0 {
+            if rows.last().is_none_or(|row| row[0] != outcome.input) {
+                rows.push([outcome.input.clone(), String::new(), String::new()]);
+            }
+            let mut cell = match &outcome.answer {
+                Ok(answer) => answer.clone(),
+                Err(err) => format!("error: {}", err),
+            };
+            if let Some(expected) = outcome.expected.as_ref().filter(|_| outcome.drifted()) {
+                cell = format!("{} (expected {})", cell, expected);
+            }
+            rows.last_mut().unwrap()[outcome.part as usize] = cell;
+        }
+
+        let day = self.0.first().map_or(0, |outcome| outcome.day);
+        let header = [
+            format!("day {}", day),
+            "part 1".to_string(),
+            "part 2".to_string(),
+        ];
+        let widths: Vec<usize> = (0..3)
+            .map(|column| {
+                rows.iter()
+                    .chain([&header])
+                    .map(|row| row[column].len())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        for row in [&header].into_iter().chain(&rows) {
+            writeln!(
+                f,
+                "{:<w0$}  {:<w1$}  {}",
+                row[0],
+                row[1],
+                row[2],
+                w0 = widths[0],
+                w1 = widths[1]
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Version of the [`Record`] schema, bumped whenever a field changes meaning
+/// or goes away. New fields may appear without a bump.
+pub const SCHEMA: u32 = 1;
+
+/// An [`Outcome`] as printed by `--format json`, one object per line.
+/// Exactly one of `answer` and `error` is set.
+#[derive(Debug, Serialize)]
+pub struct Record<'a> {
+    pub schema: u32,
+    pub day: u32,
+    pub input: &'a str,
+    pub part: u8,
+    pub answer: Option<&'a str>,
+    pub error: Option<&'a str>,
+    pub expected: Option<&'a str>,
+    pub drifted: bool,
+    pub elapsed_us: u64,
+    pub input_sha256: Option<&'a str>,
+}
+
+impl<'a> From<&'a Outcome> for Record<'a> {
+    fn from(outcome: &'a Outcome) -> Self {
+        Record {
+            schema: SCHEMA,
+            day: outcome.day,
+            input: &outcome.input,
+            part: outcome.part,
+            answer: outcome.answer.as_deref().ok(),
+            error: outcome.answer.as_ref().err().map(String::as_str),
+            expected: outcome.expected.as_deref(),
+            drifted: outcome.drifted(),
+            elapsed_us: outcome.elapsed.as_micros().try_into().unwrap_or(u64::MAX),
+            input_sha256: outcome.input_sha256.as_deref(),
+        }
+    }
+}
+
+impl fmt::Display for Record<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| fmt::Error)?;
+        write!(f, "{}", json)
+    }
+}
+
+/// How `aoc run` prints its outcomes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum Format {
+    /// One line per part, or a table with --inputs.
+    #[default]
+    Text,
+    /// One [`Record`] per part, as JSON lines.
+    Json,
+}
+
+/// How input files are handed to the solutions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum Reader {
+    /// Read the whole file and normalize its line endings.
+    #[default]
+    Read,
+    /// Map the file into memory and solve it in place.
+    Mmap,
+    /// Feed the file one line at a time, for inputs larger than memory.
+    Stream,
+}
+
+/// One part of one day, written `5.2` for day 5 part 2.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DayPart {
+    pub day: u32,
+    pub part: u8,
+}
+
+impl FromStr for DayPart {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid part '{}', expected DAY.PART such as 5.2", s);
+        let (day, part) = s.split_once('.').ok_or_else(invalid)?;
+        match (day.parse(), part.parse()) {
+            (Ok(day), Ok(part @ 1..=2)) => Ok(DayPart { day, part }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Parts of `day` not listed in `skip`.
+fn parts(day: u32, skip: &[DayPart]) -> Vec<u8> {
+    [1, 2]
+        .into_iter()
+        .filter(|part| !skip.contains(&DayPart { day, part: *part }))
+        .collect()
+}
+
+fn solve_file(
+    day: u32,
+    path: &Path,
+    part: u8,
+    reader: Reader,
+) -> (Result<String, String>, Duration, Option<String>) {
+    let start = Instant::now();
+    let answer = match days::get(day) {
+        Some(solution) => read_and_solve(solution, path, part, reader),
+        None => Err(format!("day {} is not solved", day)),
+    };
+    (answer, start.elapsed(), sha256(path).ok())
+}
+
+/// Hex SHA-256 of the file at `path`, read in chunks so that inputs larger
+/// than memory can be hashed too.
+fn sha256(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+fn read_and_solve(
+    solution: &dyn Solution,
+    path: &Path,
+    part: u8,
+    reader: Reader,
+) -> Result<String, String> {
+    let io_error = |err: io::Error| format!("{}: {}", path.display(), err);
+    let answer = match reader {
+        Reader::Read => solution.solve(part, &utils::read_file(path).map_err(io_error)?),
+        Reader::Mmap => {
+            let mapped = utils::map_file(path).map_err(io_error)?;
+            solution.solve(part, mapped.as_str().map_err(io_error)?)
+        }
+        Reader::Stream => {
+            solution.solve_lines(part, &mut utils::read_lines(path).map_err(io_error)?)
+        }
+    };
+    answer.map_err(|err| err.to_string())
+}
+
+/// Solves one part of `day` on `input`, a file in the day's directory.
+pub fn solve(config: &Config, day: u32, input: &str, part: u8, reader: Reader) -> Outcome {
+    let (answer, elapsed, input_sha256) =
+        solve_file(day, &config.day_dir(day).join(input), part, reader);
+    Outcome {
+        day,
+        input: input.to_string(),
+        part,
+        answer,
+        expected: None,
+        elapsed,
+        input_sha256,
+    }
+}
+
+/// Files with a `.txt` extension in `dir`, sorted by name.
+pub fn input_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|extension| extension == "txt") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Solves both parts of `day`, except those in `skip`, on each of `inputs`
+/// in parallel. Outcomes come back in input order, and inputs inside the
+/// day's directory are checked against the accepted answers.
+pub fn solve_all(
+    config: &Config,
+    day: u32,
+    inputs: &[PathBuf],
+    reader: Reader,
+    skip: &[DayPart],
+) -> Result<Vec<Outcome>, AnswersError> {
+    let answers = Answers::load(&config.answers_path(day))?;
+    let day_dir = config.day_dir(day);
+    let parts = parts(day, skip);
+    Ok(inputs
+        .par_iter()
+        .flat_map(|path| parts.par_iter().map(move |part| (path, *part)))
+        .map(|(path, part)| {
+            let input = path
+                .strip_prefix(&day_dir)
+                .unwrap_or(path)
+                .display()
+                .to_string();
+            let (answer, elapsed, input_sha256) = solve_file(day, path, part, reader);
+            Outcome {
+                day,
+                expected: answers.get(&input, part).map(str::to_string),
+                input,
+                part,
+                answer,
+                elapsed,
+                input_sha256,
+            }
+        })
+        .collect())
+}
+
+/// Solves both parts of `day`, except those in `skip`, on its puzzle input.
+pub fn run(
+    config: &Config,
+    day: u32,
+    reader: Reader,
+    skip: &[DayPart],
+) -> Result<Vec<Outcome>, AnswersError> {
+    let answers = Answers::load(&config.answers_path(day))?;
+    Ok(parts(day, skip)
+        .into_iter()
+        .map(|part| Outcome {
+            expected: answers.get(INPUT, part).map(str::to_string),
+            ..solve(config, day, INPUT, part, reader)
+        })
+        .collect())
+}
+
+/// Re-solves every part of `day` that has an accepted answer, on every input
+/// that has one, except the parts in `skip`.
+pub fn check(
+    config: &Config,
+    day: u32,
+    reader: Reader,
+    skip: &[DayPart],
+) -> Result<Vec<Outcome>, AnswersError> {
+    let answers = Answers::load(&config.answers_path(day))?;
+    let mut outcomes = vec![];
+    for (input, expected) in &answers.inputs {
+        for part in parts(day, skip) {
+            if let Some(answer) = expected.part(part) {
+                outcomes.push(Outcome {
+                    expected: Some(answer.to_string()),
+                    ..solve(config, day, input, part, reader)
+                });
+            }
+        }
+    }
+    Ok(outcomes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn drift_is_detected() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            cache_dir: dir.path().to_path_buf(),
+            ..Config::default()
+        };
+        fs::create_dir_all(config.day_dir(9)).unwrap();
+        fs::write(
+            config.day_dir(9).join("example"),
+            include_str!("data/day9/example"),
+        )
+        .unwrap();
+        fs::write(
+            config.answers_path(9),
+            "[example]\npart1 = \"114\"\npart2 = \"3\"\n\n[missing]\npart1 = \"1\"\n",
+        )
+        .unwrap();
+
+        let outcomes = check(&config, 9, Reader::Read, &[]).unwrap();
+        let drifted: Vec<_> = outcomes
+            .iter()
+            .map(|outcome| (outcome.input.as_str(), outcome.part, outcome.drifted()))
+            .collect();
+        assert_eq!(
+            drifted,
+            [
+                ("example", 1, false),
+                ("example", 2, true),
+                ("missing", 1, true)
+            ]
+        );
+        assert!(outcomes[1].to_string().contains("2 (DRIFTED, expected 3)"));
+
+        let skip = ["9.2".parse().unwrap()];
+        let outcomes = check(&config, 9, Reader::Read, &skip).unwrap();
+        let parts: Vec<_> = outcomes
+            .iter()
+            .map(|outcome| (outcome.input.as_str(), outcome.part))
+            .collect();
+        assert_eq!(parts, [("example", 1), ("missing", 1)]);
+    }
+
+    #[test]
+    fn day_parts() {
+        assert_eq!("5.2".parse(), Ok(DayPart { day: 5, part: 2 }));
+        for invalid in ["5", "5.3", "x.1", "5.2.1"] {
+            assert!(invalid.parse::<DayPart>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn matrix_of_team_inputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            cache_dir: dir.path().to_path_buf(),
+            ..Config::default()
+        };
+        let inputs = config.day_dir(9).join("inputs");
+        fs::create_dir_all(&inputs).unwrap();
+        fs::write(inputs.join("bob.txt"), "1 2 3\n").unwrap();
+        fs::write(inputs.join("alice.txt"), include_str!("data/day9/example")).unwrap();
+        fs::write(inputs.join("notes.md"), "not an input").unwrap();
+        fs::write(
+            config.answers_path(9),
+            "[\"inputs/bob.txt\"]\npart2 = \"1\"\n",
+        )
+        .unwrap();
+
+        let files = input_files(&inputs).unwrap();
+        assert_eq!(files, [inputs.join("alice.txt"), inputs.join("bob.txt")]);
+        let outcomes = solve_all(&config, 9, &files, Reader::Read, &[]).unwrap();
+        assert_eq!(
+            Matrix(&outcomes).to_string(),
+            "day 9             part 1  part 2\n\
+             inputs/alice.txt  114     2\n\
+             inputs/bob.txt    4       0 (expected 1)\n"
+        );
+    }
+
+    #[test]
+    fn readers_agree() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            cache_dir: dir.path().to_path_buf(),
+            ..Config::default()
+        };
+        // Day 1 streams its lines, day 6 gets them collected.
+        let inputs = [
+            (
+                1,
+                include_str!("data/day1/examples/part2.txt"),
+                "part2 = \"281\"",
+            ),
+            (6, include_str!("data/day6/example"), "part1 = \"288\""),
+        ];
+        for (day, example, answer) in inputs {
+            fs::create_dir_all(config.day_dir(day)).unwrap();
+            let input = example.replace('\n', " \r\n");
+            fs::write(config.input_path(day), input).unwrap();
+            fs::write(config.answers_path(day), format!("[input]\n{}\n", answer)).unwrap();
+        }
+
+        for reader in [Reader::Read, Reader::Mmap, Reader::Stream] {
+            for (day, _, _) in inputs {
+                let outcomes = check(&config, day, reader, &[]).unwrap();
+                assert_eq!(outcomes.len(), 1);
+                assert!(!outcomes[0].drifted(), "{:?}", outcomes[0]);
+            }
+        }
+    }
+
+    #[test]
+    fn unlocked_parts_do_not_drift() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            cache_dir: dir.path().to_path_buf(),
+            ..Config::default()
+        };
+        let outcomes = run(&config, 7, Reader::Read, &[]).unwrap();
+        assert_eq!(outcomes.len(), 2);
+        assert!(outcomes.iter().all(|outcome| outcome.answer.is_err()));
+        assert!(!outcomes.iter().any(Outcome::drifted));
+    }
+
+    #[test]
+    fn json_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            cache_dir: dir.path().to_path_buf(),
+            ..Config::default()
+        };
+        fs::create_dir_all(config.day_dir(9)).unwrap();
+        fs::write(config.input_path(9), "abc").unwrap();
+        fs::write(config.answers_path(9), "[input]\npart1 = \"1\"\n").unwrap();
+
+        let outcomes = run(&config, 9, Reader::Read, &[]).unwrap();
+        let records: Vec<serde_json::Value> = outcomes
+            .iter()
+            .map(|outcome| serde_json::from_str(&Record::from(outcome).to_string()).unwrap())
+            .collect();
+        let record = &records[0];
+        assert_eq!(record["schema"], SCHEMA);
+        assert_eq!(record["day"], 9);
+        assert_eq!(record["input"], "input");
+        assert_eq!(record["part"], 1);
+        assert_eq!(record["answer"], serde_json::Value::Null);
+        assert_eq!(record["error"], "invalid number 'abc'");
+        assert_eq!(record["expected"], "1");
+        assert_eq!(record["drifted"], true);
+        assert!(record["elapsed_us"].is_u64());
+        // echo -n abc | sha256sum
+        assert_eq!(
+            record["input_sha256"],
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(records[1]["expected"], serde_json::Value::Null);
+        assert_eq!(records[1]["drifted"], false);
+    }
+}
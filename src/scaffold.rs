@@ -0,0 +1,302 @@
+//! Generates the files of a new day: the solution module, registered in
+//! `src/days/mod.rs`, a thin binary and the examples directory.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+
+/// The first example, below the directory of the day.
+const EXAMPLE: &str = "examples/part1.txt";
+
+const MODULE: &str = r#"use super::{Error, Solution, Unsolved};
+
+pub fn part1(_input: &str) -> Result<u64, Error> {
+    Err(Unsolved.into())
+}
+
+pub fn part2(_input: &str) -> Result<u64, Error> {
+    Err(Unsolved.into())
+}
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    fn part1(&self, input: &str) -> Result<String, Error> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, Error> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!({example});
+
+    #[test]
+    #[ignore = "fill in the example and its answer"]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 0);
+    }
+}
+"#;
+
+const BIN: &str = r#"use aoc2023::days::day{day}::Day{day};
+use aoc2023::days::Solution;
+use aoc2023::utils;
+
+fn main() {
+    match utils::read_file({input}) {
+        Ok(input) => {
+            for part in [1, 2] {
+                match Day{day}.solve(part, &input) {
+                    Ok(answer) => println!("{}", answer),
+                    Err(err) => eprintln!("Error: {}", err),
+                }
+            }
+        }
+        Err(err) => {
+            eprintln!("Error reading file: {}", err);
+        }
+    }
+}
+"#;
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// Files that would have been overwritten; nothing was written.
+    Exists(Vec<PathBuf>),
+    /// The module list in `src/days/mod.rs` could not be found.
+    Unregistered(PathBuf),
+    Io(PathBuf, io::Error),
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScaffoldError::Exists(paths) => {
+                write!(f, "refusing to overwrite")?;
+                for path in paths {
+                    write!(f, " {}", path.display())?;
+                }
+                Ok(())
+            }
+            ScaffoldError::Unregistered(path) => {
+                write!(f, "cannot find the list of days in {}", path.display())
+            }
+            ScaffoldError::Io(path, err) => write!(f, "cannot access {}: {}", path.display(), err),
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+/// `path`, relative to the directory holding `Cargo.toml` unless absolute,
+/// as seen from `src/days/` where `include_str!` resolves it.
+fn from_days_dir(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    match path.strip_prefix("src") {
+        Ok(rest) => Path::new("..").join(rest),
+        Err(_) => Path::new("../..").join(path),
+    }
+}
+
+/// `template` for `day`, with the paths it reads taken from `config`.
+fn render(template: &str, config: &Config, day: u32) -> String {
+    let example = config.day_dir(day).join(EXAMPLE);
+    template
+        .replace("{day}", &day.to_string())
+        .replace(
+            "{example}",
+            &format!("{:?}", from_days_dir(&example).display().to_string()),
+        )
+        .replace(
+            "{input}",
+            &format!("{:?}", config.input_path(day).display().to_string()),
+        )
+}
+
+/// Day of a `pub mod dayN;` or `(N, &dayN::DayN),` line.
+fn registered_day(line: &str) -> Option<u32> {
+    let line = line.trim();
+    if let Some(day) = line
+        .strip_prefix("pub mod day")
+        .and_then(|rest| rest.strip_suffix(';'))
+    {
+        return day.parse().ok();
+    }
+    let (day, rest) = line.strip_prefix('(')?.split_once(", &day")?;
+    rest.ends_with("),").then(|| day.parse().ok())?
+}
+
+/// Inserts `day` into both lists of `src/days/mod.rs`, keeping them sorted.
+fn register(module: &str, day: u32) -> Option<String> {
+    let mut lines: Vec<String> = module.lines().map(str::to_string).collect();
+    // rustfmt sorts module declarations as text, so day10 comes before day2
+    // there, while the solutions stay in numeric order.
+    let entries = [
+        ("pub mod day", format!("pub mod day{};", day), true),
+        (
+            "    (",
+            format!("    ({}, &day{}::Day{}),", day, day, day),
+            false,
+        ),
+    ];
+    for (prefix, entry, textual) in entries {
+        let key = |day: u32| {
+            if textual {
+                day.to_string()
+            } else {
+                format!("{:010}", day)
+            }
+        };
+        let listed: Vec<(usize, u32)> = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.starts_with(prefix))
+            .filter_map(|(index, line)| Some((index, registered_day(line)?)))
+            .collect();
+        let (last, _) = listed.last()?;
+        let index = listed
+            .iter()
+            .find(|(_, listed)| key(*listed) > key(day))
+            .map_or(last + 1, |(index, _)| *index);
+        lines.insert(index, entry);
+    }
+    Some(lines.join("\n") + "\n")
+}
+
+/// Creates the files of `day` below `root`, the directory holding
+/// `Cargo.toml`, and returns the paths it wrote. Nothing is written if any
+/// of the files already exists.
+pub fn scaffold(root: &Path, config: &Config, day: u32) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let module_path = root.join("src/days").join(format!("day{}.rs", day));
+    let bin_path = root.join("src/bin").join(format!("day{}.rs", day));
+    let example_path = root.join(config.day_dir(day)).join(EXAMPLE);
+    let mod_path = root.join("src/days/mod.rs");
+
+    let existing: Vec<PathBuf> = [&module_path, &bin_path, &example_path]
+        .into_iter()
+        .filter(|path| path.exists())
+        .cloned()
+        .collect();
+    if !existing.is_empty() {
+        return Err(ScaffoldError::Exists(existing));
+    }
+
+    let module =
+        fs::read_to_string(&mod_path).map_err(|err| ScaffoldError::Io(mod_path.clone(), err))?;
+    if module.lines().any(|line| registered_day(line) == Some(day)) {
+        return Err(ScaffoldError::Exists(vec![mod_path]));
+    }
+    let module = register(&module, day).ok_or(ScaffoldError::Unregistered(mod_path.clone()))?;
+
+    let files = [
+        (module_path, render(MODULE, config, day)),
+        (bin_path, render(BIN, config, day)),
+        (example_path, String::new()),
+        (mod_path, module),
+    ];
+    let mut written = vec![];
+    for (path, content) in files {
+        fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| fs::write(&path, content))
+            .map_err(|err| ScaffoldError::Io(path.clone(), err))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_in_order() {
+        let module = "pub mod day1;\npub mod day3;\n\nstatic SOLUTIONS: &[(u32, &dyn Solution)] = &[\n    (1, &day1::Day1),\n    (3, &day3::Day3),\n];\n";
+        assert_eq!(
+            register(module, 2).unwrap(),
+            "pub mod day1;\npub mod day2;\npub mod day3;\n\nstatic SOLUTIONS: &[(u32, &dyn Solution)] = &[\n    (1, &day1::Day1),\n    (2, &day2::Day2),\n    (3, &day3::Day3),\n];\n"
+        );
+        let module = register(module, 12).unwrap();
+        assert!(module.contains("pub mod day1;\npub mod day12;\npub mod day3;\n"));
+        assert!(module.contains("    (3, &day3::Day3),\n    (12, &day12::Day12),\n"));
+        assert_eq!(register("fn main() {}\n", 2), None);
+    }
+
+    #[test]
+    fn refuses_to_overwrite() {
+        let root = tempfile::tempdir().unwrap();
+        let config = Config::default();
+        fs::create_dir_all(root.path().join("src/days")).unwrap();
+        fs::write(
+            root.path().join("src/days/mod.rs"),
+            include_str!("days/mod.rs"),
+        )
+        .unwrap();
+
+        let written = scaffold(root.path(), &config, 10).unwrap();
+        assert_eq!(written.len(), 4);
+        let module = fs::read_to_string(root.path().join("src/days/mod.rs")).unwrap();
+        assert!(module.contains("pub mod day10;\n"));
+        assert!(module.contains("    (10, &day10::Day10),\n];"));
+        assert!(root
+            .path()
+            .join("src/data/day10/examples/part1.txt")
+            .exists());
+        assert!(!root.path().join("src/data/day10/input").exists());
+
+        match scaffold(root.path(), &config, 10) {
+            Err(ScaffoldError::Exists(paths)) => assert_eq!(paths.len(), 3),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(
+            fs::read_to_string(root.path().join("src/days/mod.rs")).unwrap(),
+            module
+        );
+
+        fs::remove_file(root.path().join("src/days/day10.rs")).unwrap();
+        fs::remove_file(root.path().join("src/bin/day10.rs")).unwrap();
+        fs::remove_dir_all(root.path().join("src/data/day10")).unwrap();
+        assert!(matches!(
+            scaffold(root.path(), &config, 10),
+            Err(ScaffoldError::Exists(_))
+        ));
+    }
+
+    #[test]
+    fn paths_follow_the_cache_dir() {
+        let root = tempfile::tempdir().unwrap();
+        let config = Config {
+            cache_dir: PathBuf::from("inputs"),
+            ..Config::default()
+        };
+        fs::create_dir_all(root.path().join("src/days")).unwrap();
+        fs::write(
+            root.path().join("src/days/mod.rs"),
+            include_str!("days/mod.rs"),
+        )
+        .unwrap();
+
+        scaffold(root.path(), &config, 10).unwrap();
+        let module = fs::read_to_string(root.path().join("src/days/day10.rs")).unwrap();
+        let example = "../../inputs/day10/examples/part1.txt";
+        assert!(module.contains(&format!("include_str!(\"{}\")", example)));
+        assert!(root.path().join("src/days").join(example).exists());
+        let bin = fs::read_to_string(root.path().join("src/bin/day10.rs")).unwrap();
+        assert!(bin.contains("utils::read_file(\"inputs/day10/input\")"));
+
+        let module = render(MODULE, &Config::default(), 10);
+        assert!(module.contains("include_str!(\"../data/day10/examples/part1.txt\")"));
+        let bin = render(BIN, &Config::default(), 10);
+        assert!(bin.contains("utils::read_file(\"src/data/day10/input\")"));
+    }
+}
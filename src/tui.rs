@@ -0,0 +1,619 @@
+//! Terminal UI stepping through the solutions of some days: the day 4 card
+//! copies propagating, the day 8 ghosts walking the network and the day 9
+//! difference pyramids being built. Only built with the `tui` feature.
+
+use std::io;
+use std::ops;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListState, Paragraph, Widget, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::config::Config;
+use crate::days::day4::{self, Card, Cascade, OverflowPolicy};
+use crate::days::day8::{self, Documents, Network, NetworkError, Walk};
+use crate::days::day9::{self, HistoryError, Pyramid};
+use crate::days::Error;
+use crate::parse;
+use crate::utils;
+
+/// A solution run one step at a time.
+pub trait Stepper {
+    fn is_done(&self) -> bool;
+
+    fn step(&mut self) -> Result<(), Error>;
+
+    /// Progress so far, or the answer once done.
+    fn status(&self) -> String;
+
+    fn render(&self, area: Rect, buf: &mut Buffer);
+}
+
+type Loader = for<'a> fn(&'a str) -> Result<Box<dyn Stepper + 'a>, Error>;
+
+static STEPPERS: &[(u32, &str, Loader)] = &[
+    (4, "scratchcard copies propagating", |input| {
+        Ok(Box::new(Cards::new(input)?))
+    }),
+    (8, "ghosts walking the network", |input| {
+        Ok(Box::new(Ghosts::new(input)?))
+    }),
+    (9, "difference pyramids", |input| {
+        Ok(Box::new(Pyramids::new(input)?))
+    }),
+];
+
+/// Stepper of `day` on `input`, or `None` if the day cannot be stepped
+/// through.
+pub fn stepper(day: u32, input: &str) -> Option<Result<Box<dyn Stepper + '_>, Error>> {
+    let (_, _, load) = STEPPERS.iter().find(|(stepped, _, _)| *stepped == day)?;
+    Some(load(input))
+}
+
+/// Every day that can be stepped through, in order.
+pub fn days() -> impl Iterator<Item = u32> {
+    STEPPERS.iter().map(|(day, _, _)| *day)
+}
+
+/// Day 4: the copies each card wins, one card at a time.
+pub struct Cards {
+    pub cascade: Cascade,
+    /// The cards won by the last card processed.
+    pub won: ops::Range<usize>,
+}
+
+impl Cards {
+    pub fn new(input: &str) -> Result<Self, Error> {
+        let matches = day4::parse_cards(input)?
+            .iter()
+            .map(Card::matches)
+            .collect();
+        Ok(Self {
+            cascade: Cascade::new(matches, OverflowPolicy::Clamp),
+            won: 0..0,
+        })
+    }
+}
+
+impl Stepper for Cards {
+    fn is_done(&self) -> bool {
+        self.cascade.is_done()
+    }
+
+    fn step(&mut self) -> Result<(), Error> {
+        self.won = self.cascade.step()?;
+        Ok(())
+    }
+
+    fn status(&self) -> String {
+        if self.is_done() {
+            match day4::total_copies(&self.cascade.copies) {
+                Ok(total) => format!("{} scratchcards in the end", total),
+                Err(err) => err.to_string(),
+            }
+        } else {
+            format!(
+                "card {} of {}",
+                self.cascade.card + 1,
+                self.cascade.matches.len()
+            )
+        }
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let card = self.cascade.card;
+        let first = card.saturating_sub(area.height as usize / 2);
+        let lines: Vec<Line> = self
+            .cascade
+            .matches
+            .iter()
+            .zip(&self.cascade.copies)
+            .enumerate()
+            .skip(first)
+            .take(area.height as usize)
+            .map(|(index, (matches, copies))| {
+                let style = if index + 1 == card {
+                    Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                } else if self.won.contains(&index) {
+                    Style::new().fg(Color::Green)
+                } else if index < card {
+                    Style::new().add_modifier(Modifier::DIM)
+                } else {
+                    Style::new()
+                };
+                Line::styled(
+                    format!(
+                        "Card {:>4}: {:>2} matches {:>12} copies",
+                        index + 1,
+                        matches,
+                        copies
+                    ),
+                    style,
+                )
+            })
+            .collect();
+        Paragraph::new(lines).render(area, buf);
+    }
+}
+
+/// Day 8: every ghost starting on a node ending in A walks until it reaches
+/// a node ending in Z.
+pub struct Ghosts<'a> {
+    pub documents: Documents<'a>,
+    pub network: Network<'a>,
+    pub walks: Vec<Walk<'a>>,
+}
+
+impl<'a> Ghosts<'a> {
+    pub fn new(input: &'a str) -> Result<Self, Error> {
+        let documents = parse::parse_all(day8::parse, input)?;
+        let network = documents.network()?;
+        let mut starts: Vec<&str> = network
+            .keys()
+            .copied()
+            .filter(|node| node.ends_with('A'))
+            .collect();
+        if starts.is_empty() {
+            return Err(NetworkError::NoStart.into());
+        }
+        starts.sort();
+        Ok(Self {
+            walks: starts.into_iter().map(Walk::new).collect(),
+            documents,
+            network,
+        })
+    }
+
+    fn steps(&self) -> u64 {
+        self.walks.iter().map(|walk| walk.steps).max().unwrap_or(0)
+    }
+}
+
+impl Stepper for Ghosts<'_> {
+    fn is_done(&self) -> bool {
+        self.walks.iter().all(Walk::at_exit)
+    }
+
+    fn step(&mut self) -> Result<(), Error> {
+        let lr_steps = &self.documents.lr_steps;
+        let limit = day8::step_limit(&self.network, lr_steps);
+        for walk in self.walks.iter_mut().filter(|walk| !walk.at_exit()) {
+            if walk.steps > limit {
+                return Err(NetworkError::NoExit(walk.start.to_string()).into());
+            }
+            walk.step(&self.network, lr_steps)?;
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> String {
+        if self.is_done() {
+            let steps: Vec<u64> = self.walks.iter().map(|walk| walk.steps).collect();
+            match day8::lcm(&steps) {
+                Some(steps) => format!("every ghost is on a Z node after {} steps", steps),
+                None => NetworkError::Overflow.to_string(),
+            }
+        } else {
+            format!("step {}", self.steps())
+        }
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let lr_steps = &self.documents.lr_steps;
+        let next = self
+            .walks
+            .iter()
+            .find(|walk| !walk.at_exit())
+            .map(|walk| walk.instruction(lr_steps));
+        let instructions: Vec<Span> = lr_steps
+            .iter()
+            .enumerate()
+            .map(|(index, step)| {
+                if Some(index) == next {
+                    step.to_string().black().on_yellow()
+                } else {
+                    step.to_string().dim()
+                }
+            })
+            .collect();
+        let [top, bottom] =
+            Layout::vertical([Constraint::Length(4), Constraint::Fill(1)]).areas(area);
+        Paragraph::new(Line::from(instructions))
+            .wrap(Wrap { trim: false })
+            .block(Block::bordered().title("instructions"))
+            .render(top, buf);
+
+        let lines: Vec<Line> = self
+            .walks
+            .iter()
+            .map(|walk| {
+                let line = format!("{} -> {} after {} steps", walk.start, walk.node, walk.steps);
+                if walk.at_exit() {
+                    line.green().into()
+                } else {
+                    line.into()
+                }
+            })
+            .collect();
+        Paragraph::new(lines).render(bottom, buf);
+    }
+}
+
+/// Day 9: the differences of each history down to a row of zeros, then the
+/// values extrapolated at both ends of every row.
+pub struct Pyramids {
+    pub histories: Vec<Vec<i64>>,
+    /// Index of the history being shown.
+    pub history: usize,
+    pub pyramid: Pyramid,
+    /// The previous and next values of every level, once it is complete.
+    pub ends: Option<Vec<(i64, i64)>>,
+    /// Sums of the next and previous values of the histories done so far.
+    pub sums: (i64, i64),
+}
+
+impl Pyramids {
+    pub fn new(input: &str) -> Result<Self, Error> {
+        let histories = input
+            .lines()
+            .map(day9::history)
+            .collect::<Result<Vec<_>, _>>()?;
+        let Some(first) = histories.first() else {
+            return Err("there are no histories".into());
+        };
+        Ok(Self {
+            pyramid: Pyramid::start(first),
+            histories,
+            history: 0,
+            ends: None,
+            sums: (0, 0),
+        })
+    }
+}
+
+impl Stepper for Pyramids {
+    fn is_done(&self) -> bool {
+        self.ends.is_some() && self.history + 1 >= self.histories.len()
+    }
+
+    fn step(&mut self) -> Result<(), Error> {
+        if self.ends.is_some() {
+            self.history += 1;
+            self.pyramid = Pyramid::start(&self.histories[self.history]);
+            self.ends = None;
+        } else if self.pyramid.is_complete() {
+            let (next, previous) = (self.pyramid.next_values()?, self.pyramid.previous_values()?);
+            self.sums = (
+                self.sums
+                    .0
+                    .checked_add(next[0])
+                    .ok_or(HistoryError::Overflow)?,
+                self.sums
+                    .1
+                    .checked_add(previous[0])
+                    .ok_or(HistoryError::Overflow)?,
+            );
+            self.ends = Some(previous.into_iter().zip(next).collect());
+        } else {
+            self.pyramid.step()?;
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> String {
+        format!(
+            "history {} of {}, next values sum to {}, previous values to {}",
+            self.history + 1,
+            self.histories.len(),
+            self.sums.0,
+            self.sums.1
+        )
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let levels = &self.pyramid.levels;
+        let ends: Vec<Option<(i64, i64)>> = (0..levels.len())
+            .map(|level| self.ends.as_ref().map(|ends| ends[level]))
+            .collect();
+        let width = levels
+            .iter()
+            .flatten()
+            .copied()
+            .chain(
+                ends.iter()
+                    .flatten()
+                    .flat_map(|(first, last)| [*first, *last]),
+            )
+            .map(|value| value.to_string().len())
+            .max()
+            .unwrap_or(1)
+            + 2;
+        let cell = |value: i64| format!("{:>width$}", value, width = width);
+        let lines: Vec<Line> = levels
+            .iter()
+            .zip(&ends)
+            .enumerate()
+            .map(|(depth, (row, ends))| {
+                let mut spans = vec![Span::raw(" ".repeat(depth * width / 2))];
+                match ends {
+                    Some((first, _)) => spans.push(cell(*first).magenta()),
+                    None => spans.push(Span::raw(" ".repeat(width))),
+                }
+                spans.extend(row.iter().map(|value| Span::raw(cell(*value))));
+                if let Some((_, last)) = ends {
+                    spans.push(cell(*last).green());
+                }
+                Line::from(spans)
+            })
+            .collect();
+        Paragraph::new(lines).render(area, buf);
+    }
+}
+
+/// What to do after leaving a stepper.
+enum Exit {
+    Back(Option<String>),
+    Quit,
+}
+
+/// Runs the UI until it is quit, starting on `day` if given. Days are read
+/// from `input`, or from their cached puzzle input.
+pub fn run(config: &Config, day: Option<u32>, input: Option<PathBuf>) -> Result<(), Error> {
+    let mut terminal = ratatui::init();
+    let result = pick(&mut terminal, config, day, input);
+    ratatui::restore();
+    result
+}
+
+fn pick(
+    terminal: &mut DefaultTerminal,
+    config: &Config,
+    mut day: Option<u32>,
+    input: Option<PathBuf>,
+) -> Result<(), Error> {
+    let mut selected = ListState::default().with_selected(Some(0));
+    let mut message = None;
+    loop {
+        if let Some(picked) = day.take() {
+            let path = input.clone().unwrap_or_else(|| config.input_path(picked));
+            match step_through(terminal, picked, &path)? {
+                Exit::Back(error) => message = error,
+                Exit::Quit => return Ok(()),
+            }
+        }
+        terminal.draw(|frame| draw_picker(frame, &mut selected, message.as_deref()))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Up | KeyCode::Char('k') => selected.select_previous(),
+                KeyCode::Down | KeyCode::Char('j') => selected.select_next(),
+                KeyCode::Enter => {
+                    let index = selected.selected().unwrap_or(0).min(STEPPERS.len() - 1);
+                    day = Some(STEPPERS[index].0);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn draw_picker(frame: &mut Frame, selected: &mut ListState, message: Option<&str>) {
+    let [list, status] =
+        Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+    let items = STEPPERS
+        .iter()
+        .map(|(day, description, _)| format!("day {}  {}", day, description));
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(Block::bordered().title("pick a day"))
+            .highlight_style(Style::new().reversed()),
+        list,
+        selected,
+    );
+    let status_line = match message {
+        Some(message) => Line::from(message.to_string().red()),
+        None => Line::from("↑/↓ pick, enter step through, q quit".dim()),
+    };
+    frame.render_widget(status_line, status);
+}
+
+fn step_through(terminal: &mut DefaultTerminal, day: u32, path: &Path) -> io::Result<Exit> {
+    let input = match utils::read_file(path) {
+        Ok(input) => input,
+        Err(err) => return Ok(Exit::Back(Some(format!("{}: {}", path.display(), err)))),
+    };
+    let load = |input| stepper(day, input).unwrap_or_else(|| Err("not steppable".into()));
+    let mut stepper = match load(&input) {
+        Ok(stepper) => stepper,
+        Err(err) => return Ok(Exit::Back(Some(format!("day {}: {}", day, err)))),
+    };
+    let mut error: Option<String> = None;
+    let mut playing = false;
+    loop {
+        terminal.draw(|frame| {
+            draw_stepper(
+                frame,
+                day,
+                path,
+                stepper.as_ref(),
+                error.as_deref(),
+                playing,
+            )
+        })?;
+        let timeout = if playing {
+            Duration::from_millis(50)
+        } else {
+            Duration::from_secs(60)
+        };
+        if !event::poll(timeout)? {
+            if playing {
+                playing = advance(stepper.as_mut(), &mut error);
+            }
+            continue;
+        }
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') => return Ok(Exit::Quit),
+                KeyCode::Esc | KeyCode::Backspace => return Ok(Exit::Back(None)),
+                KeyCode::Char(' ') | KeyCode::Right => {
+                    advance(stepper.as_mut(), &mut error);
+                }
+                KeyCode::Char('p') => playing = !playing,
+                KeyCode::Char('e') => while advance(stepper.as_mut(), &mut error) {},
+                KeyCode::Char('r') => {
+                    // The input loaded before, so it loads again.
+                    stepper = load(&input).map_err(|err| io::Error::other(err.to_string()))?;
+                    error = None;
+                    playing = false;
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Takes one step unless the stepper is done or failed, and returns whether
+/// there are steps left.
+fn advance(stepper: &mut dyn Stepper, error: &mut Option<String>) -> bool {
+    if error.is_some() || stepper.is_done() {
+        return false;
+    }
+    if let Err(err) = stepper.step() {
+        *error = Some(err.to_string());
+        return false;
+    }
+    !stepper.is_done()
+}
+
+fn draw_stepper(
+    frame: &mut Frame,
+    day: u32,
+    path: &Path,
+    stepper: &dyn Stepper,
+    error: Option<&str>,
+    playing: bool,
+) {
+    let [main, status, help] = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let block = Block::bordered().title(format!("day {} on {}", day, path.display()));
+    stepper.render(block.inner(main), frame.buffer_mut());
+    frame.render_widget(block, main);
+    let status_line = match error {
+        Some(error) => Line::from(format!("error: {}", error).red()),
+        None if stepper.is_done() => Line::from(stepper.status().green()),
+        None => Line::from(stepper.status()),
+    };
+    frame.render_widget(status_line, status);
+    let play = if playing { "pause" } else { "play" };
+    frame.render_widget(
+        Line::from(
+            format!(
+                "space step, p {}, e run to the end, r restart, esc back, q quit",
+                play
+            )
+            .dim(),
+        ),
+        help,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_to_end(stepper: &mut dyn Stepper) -> usize {
+        let mut steps = 0;
+        while !stepper.is_done() {
+            stepper.step().unwrap();
+            steps += 1;
+        }
+        steps
+    }
+
+    fn rendered(stepper: &dyn Stepper) -> String {
+        let area = Rect::new(0, 0, 60, 12);
+        let mut buf = Buffer::empty(area);
+        stepper.render(area, &mut buf);
+        (0..area.height)
+            .map(|y| {
+                let line: String = (0..area.width).map(|x| buf[(x, y)].symbol()).collect();
+                line.trim_end().to_string() + "\n"
+            })
+            .collect()
+    }
+
+    #[test]
+    fn cards_propagate() {
+        let mut cards = Cards::new(include_str!("data/day4/examples/part1.txt")).unwrap();
+        cards.step().unwrap();
+        assert_eq!(cards.won, 1..5);
+        assert!(rendered(&cards).contains("Card    2:  2 matches            2 copies"));
+        assert_eq!(run_to_end(&mut cards), 5);
+        assert_eq!(cards.status(), "30 scratchcards in the end");
+    }
+
+    #[test]
+    fn ghosts_meet() {
+        let input = include_str!("data/day8/example");
+        let mut ghosts = Ghosts::new(input).unwrap();
+        assert_eq!(run_to_end(&mut ghosts), 3);
+        let steps: Vec<u64> = ghosts.walks.iter().map(|walk| walk.steps).collect();
+        assert_eq!(steps, [2, 3]);
+        assert_eq!(ghosts.status(), "every ghost is on a Z node after 6 steps");
+        assert!(rendered(&ghosts).contains("22A -> 22Z after 3 steps"));
+
+        let lost = "L\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n";
+        let mut ghosts = Ghosts::new(lost).unwrap();
+        let error = (0..10).find_map(|_| ghosts.step().err()).unwrap();
+        assert_eq!(
+            error.to_string(),
+            "the walk from AAA never reaches a node ending in Z"
+        );
+    }
+
+    #[test]
+    fn pyramids_are_built_row_by_row() {
+        let mut pyramids = Pyramids::new(include_str!("data/day9/example")).unwrap();
+        pyramids.step().unwrap();
+        pyramids.step().unwrap();
+        assert_eq!(
+            pyramids.pyramid.levels,
+            [vec![0, 3, 6, 9, 12, 15], vec![3; 5], vec![0; 4]]
+        );
+        assert!(pyramids.ends.is_none());
+        pyramids.step().unwrap();
+        assert_eq!(pyramids.ends, Some(vec![(-3, 18), (3, 3), (0, 0)]));
+        assert_eq!(pyramids.sums, (18, -3));
+        let text = rendered(&pyramids);
+        assert!(text.starts_with("  -3   0   3   6   9  12  15  18\n"));
+        assert!(text.contains("\n     3   3   3   3   3   3   3\n"));
+        run_to_end(&mut pyramids);
+        assert_eq!(pyramids.sums, (114, 2));
+
+        let error = Pyramids::new("").err().unwrap();
+        assert_eq!(error.to_string(), "there are no histories");
+    }
+
+    #[test]
+    fn unknown_days() {
+        assert!(stepper(1, "").is_none());
+        assert!(stepper(9, "1 x").unwrap().is_err());
+        assert_eq!(days().collect::<Vec<_>>(), [4, 8, 9]);
+    }
+}
@@ -1,7 +1,10 @@
 use std::fs::File;
-use std::io::{self, Read};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
 
-pub fn read_file(file_path: &str) -> io::Result<String> {
+use memmap2::Mmap;
+
+pub fn read_file(file_path: impl AsRef<Path>) -> io::Result<String> {
     // Open the file
     let mut file = File::open(file_path)?;
 
@@ -9,5 +12,134 @@ pub fn read_file(file_path: &str) -> io::Result<String> {
     let mut content = String::new();
     file.read_to_string(&mut content)?;
 
-    Ok(content)
+    Ok(normalize(&content))
+}
+
+/// Turns `\r\n` line endings into `\n`, strips trailing whitespace from every
+/// line and ends the text with exactly one newline, so that inputs saved on
+/// Windows or by hand parse the same as the originals.
+pub fn normalize(content: &str) -> String {
+    let mut normalized = String::with_capacity(content.len() + 1);
+    for line in content.trim_end().lines() {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+    normalized
+}
+
+/// Lines of a file read one at a time, cleaned up like [`normalize`] does:
+/// no line endings, no trailing whitespace and no blank lines at the end.
+pub struct Lines<R> {
+    lines: io::Lines<R>,
+    /// Blank lines seen but not yielded yet, since they are dropped if
+    /// nothing follows them.
+    blank: usize,
+    next: Option<String>,
+}
+
+impl<R: BufRead> Lines<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: reader.lines(),
+            blank: 0,
+            next: None,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next.is_some() {
+            if self.blank > 0 {
+                self.blank -= 1;
+                return Some(Ok(String::new()));
+            }
+            return self.next.take().map(Ok);
+        }
+        loop {
+            let mut line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(err) => return Some(Err(err)),
+            };
+            line.truncate(line.trim_end().len());
+            if line.is_empty() {
+                self.blank += 1;
+            } else if self.blank > 0 {
+                self.blank -= 1;
+                self.next = Some(line);
+                return Some(Ok(String::new()));
+            } else {
+                return Some(Ok(line));
+            }
+        }
+    }
+}
+
+/// Streams the lines of a file without holding all of it in memory.
+pub fn read_lines(file_path: impl AsRef<Path>) -> io::Result<Lines<BufReader<File>>> {
+    Ok(Lines::new(BufReader::new(File::open(file_path)?)))
+}
+
+/// A file mapped into memory, read as-is. The days accept CRLF line endings
+/// and a missing final newline, so huge inputs need not be copied to be
+/// normalized.
+pub struct Mapped(Mmap);
+
+impl Mapped {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn as_str(&self) -> io::Result<&str> {
+        std::str::from_utf8(&self.0).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+pub fn map_file(file_path: impl AsRef<Path>) -> io::Result<Mapped> {
+    let file = File::open(file_path)?;
+    // SAFETY: inputs are not modified while a day is being solved. If one
+    // is truncated anyway, reads past the new end fault instead of
+    // returning stale data.
+    let mmap = unsafe { Mmap::map(&file)? };
+    Ok(Mapped(mmap))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_line_endings_and_whitespace() {
+        assert_eq!(normalize("a \r\nb\r\n\r\nc"), "a\nb\n\nc\n");
+        assert_eq!(normalize("a\n\n\n"), "a\n");
+        assert_eq!(normalize(""), "");
+    }
+
+    #[test]
+    fn lines_match_normalize() {
+        for content in ["a \r\nb\r\n\r\nc", "a\n\n\n", "\n\na\n \n", "", "a"] {
+            let lines: Vec<String> = Lines::new(content.as_bytes())
+                .collect::<io::Result<_>>()
+                .unwrap();
+            let normalized = normalize(content);
+            let expected: Vec<&str> = normalized.lines().collect();
+            assert_eq!(lines, expected, "{:?}", content);
+        }
+    }
+
+    #[test]
+    fn mapped_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("input");
+        std::fs::write(&path, "1 2\r\n3").unwrap();
+        assert_eq!(map_file(&path).unwrap().as_str().unwrap(), "1 2\r\n3");
+
+        std::fs::write(&path, "").unwrap();
+        assert_eq!(map_file(&path).unwrap().as_bytes(), b"");
+
+        std::fs::write(&path, [0xff, 0xfe]).unwrap();
+        assert!(map_file(&path).unwrap().as_str().is_err());
+    }
 }
@@ -0,0 +1,381 @@
+//! Pictures of puzzle inputs for demos: the day 3 schematic with its part
+//! numbers and gears highlighted, the day 5 maps as flows between intervals
+//! and the day 8 network as a graph. Only built with the `viz` feature.
+
+use std::fmt::{self, Write};
+
+use clap::ValueEnum;
+
+use crate::days::day3::{self, GearRule, Schematic};
+use crate::days::day5::{self, Almanac, MAPS};
+use crate::days::day8::{self, Documents};
+use crate::days::Error;
+use crate::parse;
+
+/// Kind of picture to draw.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Format {
+    /// Coloured text for the terminal.
+    Ansi,
+    Svg,
+    /// Graphviz source.
+    Dot,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Format::Ansi => write!(f, "ansi"),
+            Format::Svg => write!(f, "svg"),
+            Format::Dot => write!(f, "dot"),
+        }
+    }
+}
+
+type Renderer = fn(&str) -> Result<String, Error>;
+
+static RENDERERS: &[(u32, Format, Renderer)] = &[
+    (3, Format::Ansi, |input| {
+        Ok(schematic_ansi(
+            &Schematic::parse(input)?,
+            &GearRule::default(),
+        ))
+    }),
+    (3, Format::Svg, |input| {
+        Ok(schematic_svg(
+            &Schematic::parse(input)?,
+            &GearRule::default(),
+        ))
+    }),
+    (5, Format::Svg, |input| {
+        Ok(almanac_svg(&parse::parse_all(day5::parse, input)?))
+    }),
+    (8, Format::Dot, |input| {
+        let documents = parse::parse_all(day8::parse, input)?;
+        documents.network()?;
+        Ok(network_dot(&documents))
+    }),
+];
+
+/// Picture of `input` for `day`, or `None` if the day cannot be drawn in
+/// that format.
+pub fn render(day: u32, format: Format, input: &str) -> Option<Result<String, Error>> {
+    let (_, _, renderer) = RENDERERS
+        .iter()
+        .find(|(drawn, drawn_format, _)| *drawn == day && *drawn_format == format)?;
+    Some(renderer(input))
+}
+
+/// Every day and format that can be drawn, in order.
+pub fn formats() -> impl Iterator<Item = (u32, Format)> {
+    RENDERERS.iter().map(|(day, format, _)| (*day, *format))
+}
+
+/// How a cell of the schematic is highlighted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Cell {
+    Empty,
+    /// A number that is not adjacent to any symbol.
+    Number,
+    PartNumber,
+    Symbol,
+    Gear,
+}
+
+/// Every cell of the schematic with its character and highlight.
+fn cells(schematic: &Schematic, rule: &GearRule) -> Vec<Vec<(char, Cell)>> {
+    let gears: Vec<usize> = day3::gears(schematic, rule)
+        .into_iter()
+        .map(|(symbol, _)| symbol)
+        .collect();
+    let mut rows: Vec<Vec<(char, Cell)>> = schematic
+        .number_at
+        .iter()
+        .map(|row| vec![('.', Cell::Empty); row.len()])
+        .collect();
+    for (index, number) in schematic.numbers.iter().enumerate() {
+        let cell = if schematic.is_part_number(index) {
+            Cell::PartNumber
+        } else {
+            Cell::Number
+        };
+        for ((i, j), digit) in number.positions().zip(number.text.chars()) {
+            rows[i][j] = (digit, cell);
+        }
+    }
+    for (index, symbol) in schematic.symbols.iter().enumerate() {
+        let (i, j) = symbol.position;
+        let cell = if gears.contains(&index) {
+            Cell::Gear
+        } else {
+            Cell::Symbol
+        };
+        rows[i][j] = (symbol.symbol, cell);
+    }
+    rows
+}
+
+/// The schematic with part numbers in green, gears in bold red, other
+/// symbols in yellow and everything else dimmed.
+pub fn schematic_ansi(schematic: &Schematic, rule: &GearRule) -> String {
+    let mut text = String::new();
+    for row in cells(schematic, rule) {
+        let mut current = None;
+        for (c, cell) in row {
+            let colour = match cell {
+                Cell::Empty | Cell::Number => "\x1b[0;2m",
+                Cell::PartNumber => "\x1b[0;32m",
+                Cell::Symbol => "\x1b[0;33m",
+                Cell::Gear => "\x1b[0;1;31m",
+            };
+            if current != Some(colour) {
+                text.push_str(colour);
+                current = Some(colour);
+            }
+            text.push(c);
+        }
+        text.push_str("\x1b[0m\n");
+    }
+    text
+}
+
+const CELL_WIDTH: usize = 10;
+const CELL_HEIGHT: usize = 16;
+
+/// `c` escaped for SVG text.
+fn escape(c: char) -> String {
+    match c {
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        '&' => "&amp;".to_string(),
+        _ => c.to_string(),
+    }
+}
+
+/// The schematic as a grid of cells, with the same colours as
+/// [`schematic_ansi`] but empty cells left out.
+pub fn schematic_svg(schematic: &Schematic, rule: &GearRule) -> String {
+    let rows = cells(schematic, rule);
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="monospace" font-size="14" text-anchor="middle">"#,
+        columns * CELL_WIDTH,
+        rows.len() * CELL_HEIGHT
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r##"<rect width="100%" height="100%" fill="#0f0f23"/>"##
+    )
+    .unwrap();
+    for (i, row) in rows.iter().enumerate() {
+        for (j, (c, cell)) in row.iter().enumerate() {
+            let (fill, weight) = match cell {
+                Cell::Empty => continue,
+                Cell::Number => ("#666666", "normal"),
+                Cell::PartNumber => ("#00cc00", "normal"),
+                Cell::Symbol => ("#ffff66", "normal"),
+                Cell::Gear => ("#ff3333", "bold"),
+            };
+            writeln!(
+                svg,
+                r#"<text x="{}" y="{}" fill="{}" font-weight="{}">{}</text>"#,
+                j * CELL_WIDTH + CELL_WIDTH / 2,
+                (i + 1) * CELL_HEIGHT - 4,
+                fill,
+                weight,
+                escape(*c)
+            )
+            .unwrap();
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// The network as a Graphviz digraph, with the start nodes (ending in A) as
+/// boxes, the exits (ending in Z) as double circles and one edge per
+/// direction, or a single `LR` edge when both lead to the same node.
+pub fn network_dot(documents: &Documents) -> String {
+    let nodes = &documents.nodes;
+    let mut dot = String::new();
+    writeln!(dot, "digraph network {{").unwrap();
+    for (node, _) in nodes {
+        if node.ends_with('A') {
+            writeln!(dot, "    \"{}\" [shape=box, color=green];", node).unwrap();
+        } else if node.ends_with('Z') {
+            writeln!(dot, "    \"{}\" [shape=doublecircle, color=red];", node).unwrap();
+        }
+    }
+    for (node, (left, right)) in nodes {
+        if left == right {
+            writeln!(dot, "    \"{}\" -> \"{}\" [label=LR];", node, left).unwrap();
+        } else {
+            writeln!(dot, "    \"{}\" -> \"{}\" [label=L];", node, left).unwrap();
+            writeln!(dot, "    \"{}\" -> \"{}\" [label=R];", node, right).unwrap();
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+const AXIS_GAP: f64 = 160.0;
+const AXIS_HEIGHT: f64 = 600.0;
+const MARGIN: f64 = 40.0;
+const PALETTE: [&str; 6] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b",
+];
+
+/// One vertical axis per category, from seed to location, with each range
+/// of a map drawn as a band from its source interval on one axis to its
+/// destination interval on the next. Numbers not covered by a range map to
+/// themselves and are left out. The seeds are ticks on the first axis.
+pub fn almanac_svg(almanac: &Almanac) -> String {
+    let mut names: Vec<&str> = MAPS
+        .iter()
+        .map(|map| map.split("-to-").next().unwrap_or(map))
+        .collect();
+    names.extend(MAPS.last().and_then(|map| map.split("-to-").nth(1)));
+
+    let max = almanac
+        .maps
+        .iter()
+        .flatten()
+        .flat_map(|range| {
+            [
+                range.src as f64 + range.len as f64,
+                range.dst as f64 + range.len as f64,
+            ]
+        })
+        .chain(almanac.seeds.iter().map(|seed| *seed as f64))
+        .fold(1.0, f64::max);
+    let y = |n: f64| MARGIN + n / max * AXIS_HEIGHT;
+    let x = |axis: usize| MARGIN + axis as f64 * AXIS_GAP;
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="sans-serif" font-size="12" text-anchor="middle">"#,
+        x(names.len() - 1) + MARGIN,
+        AXIS_HEIGHT + 2.0 * MARGIN
+    )
+    .unwrap();
+    for (axis, ranges) in almanac.maps.iter().enumerate() {
+        for (index, range) in ranges.iter().enumerate() {
+            let (src, dst, len) = (range.src as f64, range.dst as f64, range.len as f64);
+            writeln!(
+                svg,
+                r#"<polygon points="{:.1},{:.1} {:.1},{:.1} {:.1},{:.1} {:.1},{:.1}" fill="{}" fill-opacity="0.5"><title>{} map: {}</title></polygon>"#,
+                x(axis),
+                y(src),
+                x(axis),
+                y(src + len),
+                x(axis + 1),
+                y(dst + len),
+                x(axis + 1),
+                y(dst),
+                PALETTE[index % PALETTE.len()],
+                MAPS[axis],
+                range
+            )
+            .unwrap();
+        }
+    }
+    for (axis, name) in names.iter().enumerate() {
+        writeln!(
+            svg,
+            r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="black"/>"#,
+            x(axis),
+            y(0.0),
+            x(axis),
+            y(max)
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}">{}</text>"#,
+            x(axis),
+            MARGIN / 2.0,
+            name
+        )
+        .unwrap();
+    }
+    for seed in &almanac.seeds {
+        let seed = *seed as f64;
+        writeln!(
+            svg,
+            r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="black" stroke-width="2"><title>seed {}</title></line>"#,
+            x(0) - 6.0,
+            y(seed),
+            x(0),
+            y(seed),
+            seed
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schematic_highlights() {
+        let input = include_str!("data/day3/examples/part1.txt");
+        let ansi = render(3, Format::Ansi, input).unwrap().unwrap();
+        assert!(ansi.starts_with("\x1b[0;32m467\x1b[0;2m..114..\x1b[0m\n"));
+        assert!(ansi.contains("\x1b[0;33m#"));
+        assert!(ansi.contains("\x1b[0;1;31m*"));
+        assert_eq!(ansi.lines().count(), input.lines().count());
+
+        let svg = render(3, Format::Svg, input).unwrap().unwrap();
+        let schematic = Schematic::parse(input).unwrap();
+        let digits: usize = schematic
+            .numbers
+            .iter()
+            .map(|number| number.text.len())
+            .sum();
+        assert_eq!(
+            svg.matches("<text").count(),
+            digits + schematic.symbols.len()
+        );
+        let svg = render(3, Format::Svg, "1<&\n").unwrap().unwrap();
+        assert!(svg.contains(">&lt;</text>") && svg.contains(">&amp;</text>"));
+    }
+
+    #[test]
+    fn network_graph() {
+        let dot = render(8, Format::Dot, include_str!("data/day8/example"))
+            .unwrap()
+            .unwrap();
+        assert!(dot.starts_with("digraph network {\n"));
+        assert!(dot.contains("    \"11A\" [shape=box, color=green];\n"));
+        assert!(dot.contains("    \"11Z\" [shape=doublecircle, color=red];\n"));
+        assert!(dot.contains("    \"11A\" -> \"11B\" [label=L];\n"));
+        assert!(dot.contains("    \"XXX\" -> \"XXX\" [label=LR];\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn almanac_flows() {
+        let input = include_str!("data/day5/examples/part1.txt");
+        let svg = render(5, Format::Svg, input).unwrap().unwrap();
+        let almanac = parse::parse_all(day5::parse, input).unwrap();
+        let ranges: usize = almanac.maps.iter().map(Vec::len).sum();
+        assert_eq!(svg.matches("<polygon").count(), ranges);
+        assert!(svg.contains(">seed</text>") && svg.contains(">location</text>"));
+        assert!(svg.contains("<title>seed 79</title>"));
+    }
+
+    #[test]
+    fn unsupported_formats() {
+        assert!(render(8, Format::Svg, "").is_none());
+        assert!(render(1, Format::Ansi, "").is_none());
+        assert!(render(5, Format::Svg, "seeds: 1\n").unwrap().is_err());
+        assert_eq!(formats().count(), RENDERERS.len());
+    }
+}